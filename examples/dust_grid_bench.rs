@@ -0,0 +1,92 @@
+//! Headless benchmark comparing the dust grid against a linear scan.
+//!
+//! Run with `cargo run --release --example dust_grid_bench`.
+
+use std::time::{Duration, Instant};
+
+use bevy::prelude::*;
+use rand::prelude::*;
+
+#[allow(dead_code)]
+#[path = "../src/demo/gameplay/spatial.rs"]
+mod spatial;
+
+use spatial::{CELL_SIZE, DustGrid};
+
+const GAME_AREA: Rect = Rect {
+    min: Vec2::new(-200.0, -350.0),
+    max: Vec2::new(200.0, 250.0),
+};
+const LIGHTING_RANGE: f32 = 100.0;
+/// Number of chain hops simulated per frame.
+const HOPS: usize = 200;
+const FRAMES: usize = 200;
+
+fn main() {
+    let mut rng = StdRng::seed_from_u64(6);
+    println!(
+        "{:>6} {:>14} {:>14} {:>8}",
+        "dust", "scan/frame", "grid/frame", "speedup"
+    );
+    for count in [50, 200, 500, 1000, 2000, 5000] {
+        let dust: Vec<(Entity, Vec2)> = (0..count)
+            .map(|i| {
+                let pos = Vec2::new(
+                    rng.random_range(GAME_AREA.min.x..GAME_AREA.max.x),
+                    rng.random_range(GAME_AREA.min.y..GAME_AREA.max.y),
+                );
+                (Entity::from_raw(i as u32), pos)
+            })
+            .collect();
+        let sources: Vec<Vec2> = (0..HOPS).map(|i| dust[i % dust.len()].1).collect();
+
+        let (scan_time, scan_hits) = bench(|| {
+            sources
+                .iter()
+                .filter_map(|&pos| scan_nearest(&dust, pos))
+                .count()
+        });
+        let mut grid = DustGrid::new(GAME_AREA, CELL_SIZE);
+        let (grid_time, grid_hits) = bench(|| {
+            grid.clear();
+            for &(entity, pos) in &dust {
+                grid.insert(entity, pos);
+            }
+            sources
+                .iter()
+                .filter_map(|&pos| {
+                    grid.within(pos, LIGHTING_RANGE).min_by(|(_, a), (_, b)| {
+                        a.distance_squared(pos).total_cmp(&b.distance_squared(pos))
+                    })
+                })
+                .count()
+        });
+        assert_eq!(scan_hits, grid_hits, "grid and scan disagree");
+
+        println!(
+            "{:>6} {:>14?} {:>14?} {:>7.1}x",
+            count,
+            scan_time,
+            grid_time,
+            scan_time.as_secs_f64() / grid_time.as_secs_f64()
+        );
+    }
+}
+
+/// The pre-grid approach: look at every dust for every query.
+fn scan_nearest(dust: &[(Entity, Vec2)], pos: Vec2) -> Option<(Entity, Vec2)> {
+    dust.iter()
+        .copied()
+        .filter(|(_, other)| other.distance_squared(pos) < LIGHTING_RANGE * LIGHTING_RANGE)
+        .min_by(|(_, a), (_, b)| a.distance_squared(pos).total_cmp(&b.distance_squared(pos)))
+}
+
+/// Average time of one frame, including the grid rebuild for the grid case.
+fn bench(mut frame: impl FnMut() -> usize) -> (Duration, usize) {
+    let mut hits = 0;
+    let start = Instant::now();
+    for _ in 0..FRAMES {
+        hits = std::hint::black_box(frame());
+    }
+    (start.elapsed() / FRAMES as u32, hits)
+}
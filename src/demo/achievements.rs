@@ -0,0 +1,432 @@
+//! Achievements: milestones over every run, kept apart from the run's save.
+//!
+//! Achievements are declared in one table, see [`Achievement::spec`]. The
+//! gameplay events feed [`Milestones`], and an achievement unlocks as soon as
+//! its [`Requirement`] is met, with a toast.
+
+use std::collections::BTreeMap;
+
+use bevy::color::palettes::css::GOLD;
+use serde::{Deserialize, Serialize};
+
+use crate::{menus::CompleteTheGame, prelude::*, storage, theme::palette::HEADER_TEXT};
+
+use super::{
+    gameplay::{AttackDustEvent, DamageType, Dust, DustDestroyed},
+    level::CurrentLevel,
+    ui::purchase::UpgradePurchased,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.insert_resource(AchievementProgress::load());
+
+    app.add_systems(
+        Update,
+        (
+            (
+                record_destroyed_dust,
+                record_chains,
+                record_purchases,
+                record_completions,
+            ),
+            unlock_achievements,
+        )
+            .chain()
+            .run_if(in_state(Screen::Gameplay)),
+    );
+    app.add_systems(Update, tick_toasts);
+    app.add_systems(OnExit(Screen::Gameplay), store_achievements);
+    // also keep the counters when the window is closed mid-run
+    app.add_systems(
+        Last,
+        store_achievements.run_if(in_state(Screen::Gameplay).and(on_event::<AppExit>)),
+    );
+
+    app.add_observer(spawn_toast);
+}
+
+const ACHIEVEMENTS_KEY: &str = "achievements";
+/// Bump when [`AchievementProgress`] changes shape.
+const ACHIEVEMENTS_VERSION: u32 = 1;
+const TOAST_SECS: f32 = 4.0;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Achievement {
+    FirstSpark,
+    DustBuster,
+    BigGame,
+    GoldDigger,
+    GiantSlayer,
+    ChainReaction,
+    TenHops,
+    Researcher,
+    MaxedOut,
+    MissionComplete,
+    FastLearner,
+    Perfectionist,
+}
+
+pub struct AchievementSpec {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub requirement: Requirement,
+}
+
+/// What unlocks an achievement, measured on [`Milestones`].
+#[derive(Debug, Clone, Copy)]
+pub enum Requirement {
+    /// Destroy `count` dust of a species, `None` for any.
+    Destroyed { dust: Option<Dust>, count: u64 },
+    /// Hit this many dust with one lightning chain.
+    Chain(u32),
+    /// Buy this many Research Lab levels.
+    Purchases(u64),
+    /// Buy every level of this many upgrades.
+    MaxedUpgrades(u64),
+    /// Complete this many levels.
+    Completions(u64),
+    /// Complete a level within this many minutes.
+    CompletedWithin(f32),
+    /// Complete this many levels with every objective met.
+    PerfectCompletions(u64),
+}
+
+impl Achievement {
+    pub const ALL: [Self; 12] = [
+        Self::FirstSpark,
+        Self::DustBuster,
+        Self::BigGame,
+        Self::GoldDigger,
+        Self::GiantSlayer,
+        Self::ChainReaction,
+        Self::TenHops,
+        Self::Researcher,
+        Self::MaxedOut,
+        Self::MissionComplete,
+        Self::FastLearner,
+        Self::Perfectionist,
+    ];
+
+    pub fn spec(&self) -> AchievementSpec {
+        match self {
+            Self::FirstSpark => AchievementSpec {
+                name: "First Spark",
+                description: "Destroy your first dust",
+                requirement: Requirement::Destroyed {
+                    dust: None,
+                    count: 1,
+                },
+            },
+            Self::DustBuster => AchievementSpec {
+                name: "Dust Buster",
+                description: "Destroy 10000 dust",
+                requirement: Requirement::Destroyed {
+                    dust: None,
+                    count: 10_000,
+                },
+            },
+            Self::BigGame => AchievementSpec {
+                name: "Big Game",
+                description: "Destroy 1000 big dust",
+                requirement: Requirement::Destroyed {
+                    dust: Some(Dust::Big),
+                    count: 1000,
+                },
+            },
+            Self::GoldDigger => AchievementSpec {
+                name: "Gold Digger",
+                description: "Destroy 100 golden dust",
+                requirement: Requirement::Destroyed {
+                    dust: Some(Dust::Golden),
+                    count: 100,
+                },
+            },
+            Self::GiantSlayer => AchievementSpec {
+                name: "Giant Slayer",
+                description: "Destroy a mega dust",
+                requirement: Requirement::Destroyed {
+                    dust: Some(Dust::Mega),
+                    count: 1,
+                },
+            },
+            Self::ChainReaction => AchievementSpec {
+                name: "Chain Reaction",
+                description: "Hit 5 dust with one lightning chain",
+                requirement: Requirement::Chain(5),
+            },
+            Self::TenHops => AchievementSpec {
+                name: "Ten Hops",
+                description: "Hit 10 dust with one lightning chain",
+                requirement: Requirement::Chain(10),
+            },
+            Self::Researcher => AchievementSpec {
+                name: "Researcher",
+                description: "Buy 50 Research Lab upgrades",
+                requirement: Requirement::Purchases(50),
+            },
+            Self::MaxedOut => AchievementSpec {
+                name: "Maxed Out",
+                description: "Buy every level of an upgrade",
+                requirement: Requirement::MaxedUpgrades(1),
+            },
+            Self::MissionComplete => AchievementSpec {
+                name: "Mission Complete",
+                description: "Complete a level",
+                requirement: Requirement::Completions(1),
+            },
+            Self::FastLearner => AchievementSpec {
+                name: "Fast Learner",
+                description: "Complete a level within 10 minutes",
+                requirement: Requirement::CompletedWithin(10.0),
+            },
+            Self::Perfectionist => AchievementSpec {
+                name: "Perfectionist",
+                description: "Complete a level with every objective met",
+                requirement: Requirement::PerfectCompletions(1),
+            },
+        }
+    }
+}
+
+/// Everything achievements are measured on, summed over every run.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct Milestones {
+    pub destroyed: BTreeMap<Dust, u64>,
+    pub longest_chain: u32,
+    pub purchases: u64,
+    pub maxed_upgrades: u64,
+    pub completions: u64,
+    /// Seconds of the fastest completed run.
+    pub fastest_completion: Option<f32>,
+    pub perfect_completions: u64,
+}
+
+impl Requirement {
+    /// Progress towards the requirement, `None` if it isn't counted.
+    pub fn progress(&self, milestones: &Milestones) -> Option<(u64, u64)> {
+        match *self {
+            Self::Destroyed { dust, count } => {
+                let destroyed = match dust {
+                    Some(dust) => milestones.destroyed.get(&dust).copied().unwrap_or_default(),
+                    None => milestones.destroyed.values().sum(),
+                };
+                Some((destroyed, count))
+            }
+            Self::Chain(hops) => Some((milestones.longest_chain as u64, hops as u64)),
+            Self::Purchases(count) => Some((milestones.purchases, count)),
+            Self::MaxedUpgrades(count) => Some((milestones.maxed_upgrades, count)),
+            Self::Completions(count) => Some((milestones.completions, count)),
+            Self::CompletedWithin(_) => None,
+            Self::PerfectCompletions(count) => Some((milestones.perfect_completions, count)),
+        }
+    }
+
+    pub fn is_met(&self, milestones: &Milestones) -> bool {
+        match *self {
+            Self::CompletedWithin(minutes) => milestones
+                .fastest_completion
+                .is_some_and(|seconds| seconds <= minutes * 60.0),
+            _ => self
+                .progress(milestones)
+                .is_some_and(|(current, target)| current >= target),
+        }
+    }
+}
+
+/// Unlocked achievements and their [`Milestones`], stored apart from the run's
+/// save so they survive prestiges and lost runs.
+#[derive(Resource, Serialize, Deserialize, Debug, Clone)]
+pub struct AchievementProgress {
+    version: u32,
+    /// Unix seconds of each unlock.
+    unlocked: BTreeMap<Achievement, f64>,
+    pub milestones: Milestones,
+}
+
+impl Default for AchievementProgress {
+    fn default() -> Self {
+        Self {
+            version: ACHIEVEMENTS_VERSION,
+            unlocked: BTreeMap::new(),
+            milestones: Milestones::default(),
+        }
+    }
+}
+
+impl AchievementProgress {
+    /// Read the stored achievements, fresh ones if there are none.
+    fn load() -> Self {
+        let Some(contents) = storage::load(ACHIEVEMENTS_KEY) else {
+            return Self::default();
+        };
+        match ron::from_str::<Self>(&contents) {
+            Ok(progress) if progress.version <= ACHIEVEMENTS_VERSION => progress,
+            Ok(progress) => {
+                warn!(
+                    "Ignoring achievements with unsupported version {}",
+                    progress.version
+                );
+                Self::default()
+            }
+            Err(error) => {
+                warn!("Ignoring unreadable achievements: {error}");
+                Self::default()
+            }
+        }
+    }
+
+    fn store(&self) {
+        let result = ron::ser::to_string_pretty(self, default())
+            .map_err(std::io::Error::other)
+            .and_then(|contents| storage::save(ACHIEVEMENTS_KEY, &contents));
+        if let Err(error) = result {
+            error!("Could not save achievements: {error}");
+        }
+    }
+
+    pub fn is_unlocked(&self, achievement: Achievement) -> bool {
+        self.unlocked.contains_key(&achievement)
+    }
+
+    pub fn unlocked_count(&self) -> usize {
+        self.unlocked.len()
+    }
+}
+
+/// Shows a toast for a freshly unlocked achievement.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct AchievementUnlocked(pub Achievement);
+
+fn record_destroyed_dust(
+    mut events: EventReader<DustDestroyed>,
+    mut progress: ResMut<AchievementProgress>,
+) {
+    for DustDestroyed(dust) in events.read() {
+        *progress.milestones.destroyed.entry(*dust).or_default() += 1;
+    }
+}
+
+fn record_chains(
+    mut events: EventReader<AttackDustEvent>,
+    mut progress: ResMut<AchievementProgress>,
+) {
+    // only lightning chains, the other types hit their dust at once
+    let lightning = events
+        .read()
+        .filter(|event| event.damage_type == DamageType::Lightning);
+    for event in lightning {
+        let longest_chain = &mut progress.milestones.longest_chain;
+        *longest_chain = (*longest_chain).max(event.hop);
+    }
+}
+
+fn record_purchases(
+    mut events: EventReader<UpgradePurchased>,
+    mut progress: ResMut<AchievementProgress>,
+) {
+    for event in events.read() {
+        progress.milestones.purchases += 1;
+        if event.maxed {
+            progress.milestones.maxed_upgrades += 1;
+        }
+    }
+}
+
+fn record_completions(
+    mut events: EventReader<CompleteTheGame>,
+    mut progress: ResMut<AchievementProgress>,
+    level: Res<CurrentLevel>,
+) {
+    for event in events.read() {
+        let milestones = &mut progress.milestones;
+        milestones.completions += 1;
+        let seconds = event.elapsed.as_secs_f32();
+        milestones.fastest_completion = Some(
+            milestones
+                .fastest_completion
+                .map_or(seconds, |fastest| fastest.min(seconds)),
+        );
+        if event.objectives.len() == level.objectives.len() {
+            milestones.perfect_completions += 1;
+        }
+    }
+}
+
+fn unlock_achievements(mut commands: Commands, mut progress: ResMut<AchievementProgress>) {
+    // don't trigger change detection every frame
+    let newly_met = Achievement::ALL
+        .into_iter()
+        .filter(|achievement| {
+            !progress.is_unlocked(*achievement)
+                && achievement.spec().requirement.is_met(&progress.milestones)
+        })
+        .collect::<Vec<_>>();
+    if newly_met.is_empty() {
+        return;
+    }
+    let now = unix_time_secs();
+    for achievement in newly_met {
+        info!("Achievement unlocked: {}", achievement.spec().name);
+        progress.unlocked.insert(achievement, now);
+        commands.trigger(AchievementUnlocked(achievement));
+    }
+    progress.store();
+}
+
+fn store_achievements(progress: Res<AchievementProgress>) {
+    progress.store();
+}
+
+#[derive(Component, Debug)]
+struct Toast(Timer);
+
+fn spawn_toast(
+    trigger: Trigger<AchievementUnlocked>,
+    mut commands: Commands,
+    toasts: Query<(), With<Toast>>,
+) {
+    let spec = trigger.event().0.spec();
+    // stack toasts that are still showing
+    let top = 10.0 + 70.0 * toasts.iter().len() as f32;
+    commands.spawn((
+        Name::new("Achievement Toast"),
+        Toast(Timer::from_seconds(TOAST_SECS, TimerMode::Once)),
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(top),
+            left: Val::Percent(50.0),
+            width: Val::Px(360.0),
+            margin: UiRect::left(Val::Px(-180.0)),
+            padding: UiRect::all(Val::Px(8.0)),
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        BorderRadius::all(Val::Px(5.0)),
+        BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.85)),
+        GlobalZIndex(3),
+        Pickable::IGNORE,
+        children![
+            (
+                Text::new(format!("Achievement unlocked: {}", spec.name)),
+                TextFont::from_font_size(20.0),
+                TextColor(GOLD.into()),
+            ),
+            (
+                Text::new(spec.description),
+                TextFont::from_font_size(16.0),
+                TextColor(HEADER_TEXT),
+            ),
+        ],
+    ));
+}
+
+/// Toasts also count down while the game is paused, e.g. behind the complete
+/// menu.
+fn tick_toasts(mut commands: Commands, toasts: Query<(Entity, &mut Toast)>, time: Res<Time<Real>>) {
+    for (entity, mut toast) in toasts {
+        if toast.0.tick(time.delta()).finished() {
+            commands.entity(entity).despawn();
+        }
+    }
+}
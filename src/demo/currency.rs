@@ -0,0 +1,477 @@
+//! Currency: amounts of dust data and insight that keep growing long after
+//! integers and floats would overflow, and the number format they are shown
+//! in, picked in the settings menu.
+
+use std::{cmp::Ordering, fmt, iter::Sum, ops::Add, ops::AddAssign};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{prelude::*, storage};
+
+pub(super) fn plugin(app: &mut App) {
+    app.insert_resource(NumberFormat::load());
+}
+
+/// Mantissas stay below this, so whole amounts below it are exact.
+const EXACT: f64 = 1e15;
+
+/// A non-negative amount, `mantissa * 10^exponent`.
+///
+/// The exponent only grows once the mantissa would reach 10^15, so amounts
+/// below that are plain floats and exact for whole numbers. Arithmetic
+/// saturates at [`Currency::MAX`] and at zero instead of overflowing.
+#[derive(Reflect, Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+#[serde(from = "StoredCurrency", into = "StoredCurrency")]
+pub struct Currency {
+    /// Below [`EXACT`], at least a tenth of it once `exponent` is above 0.
+    mantissa: f64,
+    exponent: u32,
+}
+
+/// Stored as a plain number while it fits, so saves of older versions with
+/// integer amounts still load.
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum StoredCurrency {
+    Amount(f64),
+    Scientific { mantissa: f64, exponent: u32 },
+}
+
+impl From<StoredCurrency> for Currency {
+    fn from(stored: StoredCurrency) -> Self {
+        match stored {
+            StoredCurrency::Amount(amount) => Self::from_f64(amount),
+            StoredCurrency::Scientific { mantissa, exponent } => {
+                Self::normalize(mantissa, exponent as i64)
+            }
+        }
+    }
+}
+
+impl From<Currency> for StoredCurrency {
+    fn from(currency: Currency) -> Self {
+        if currency.exponent == 0 {
+            Self::Amount(currency.mantissa)
+        } else {
+            Self::Scientific {
+                mantissa: currency.mantissa,
+                exponent: currency.exponent,
+            }
+        }
+    }
+}
+
+impl Currency {
+    pub const ZERO: Self = Self {
+        mantissa: 0.0,
+        exponent: 0,
+    };
+    pub const MAX: Self = Self {
+        mantissa: EXACT - 1.0,
+        exponent: u32::MAX,
+    };
+
+    /// Negative and NaN amounts are zero, infinite ones [`Currency::MAX`].
+    pub fn from_f64(amount: f64) -> Self {
+        Self::normalize(amount, 0)
+    }
+
+    /// The amount `10^log10`.
+    pub fn from_log10(log10: f64) -> Self {
+        if log10.is_nan() {
+            return Self::ZERO;
+        }
+        if log10 < 15.0 {
+            return Self::from_f64(10f64.powf(log10));
+        }
+        // keep the mantissa's 15 digits, move the rest into the exponent
+        let exponent = log10.floor() - 14.0;
+        Self::normalize(10f64.powf(log10 - exponent), exponent as i64)
+    }
+
+    fn normalize(mantissa: f64, exponent: i64) -> Self {
+        if mantissa.is_nan() || mantissa <= 0.0 {
+            return Self::ZERO;
+        }
+        if mantissa.is_infinite() {
+            return Self::MAX;
+        }
+        let (mut mantissa, mut exponent) = (mantissa, exponent);
+        if mantissa >= EXACT {
+            let shift = (mantissa / EXACT).log10().floor() as i64;
+            mantissa /= 10f64.powi(shift as i32);
+            exponent += shift;
+        }
+        // rounding can leave the mantissa one digit off
+        while mantissa >= EXACT {
+            mantissa /= 10.0;
+            exponent += 1;
+        }
+        while exponent > 0 && mantissa < EXACT / 10.0 {
+            mantissa *= 10.0;
+            exponent -= 1;
+        }
+        if exponent < 0 {
+            mantissa *= 10f64.powi(exponent.max(-400) as i32);
+            exponent = 0;
+        }
+        match u32::try_from(exponent) {
+            Ok(exponent) => Self { mantissa, exponent },
+            Err(_) => Self::MAX,
+        }
+    }
+
+    /// The amount as a float, infinite once it is too large for one.
+    pub fn to_f64(self) -> f64 {
+        if self.exponent == 0 {
+            self.mantissa
+        } else {
+            self.mantissa * 10f64.powi(self.exponent.min(i32::MAX as u32) as i32)
+        }
+    }
+
+    /// Negative infinity for zero.
+    pub fn log10(self) -> f64 {
+        self.mantissa.log10() + self.exponent as f64
+    }
+
+    /// Drop the fraction, amounts with an exponent have none.
+    pub fn floor(self) -> Self {
+        Self {
+            mantissa: self.mantissa.floor(),
+            ..self
+        }
+    }
+
+    pub fn scale(self, factor: f64) -> Self {
+        Self::normalize(self.mantissa * factor, self.exponent as i64)
+    }
+
+    /// `self * base^n`, exact while the result stays small.
+    pub fn scale_pow(self, base: f64, n: usize) -> Self {
+        let exact = self.to_f64() * base.powi(n.min(i32::MAX as usize) as i32);
+        if exact.is_finite() && exact < EXACT {
+            Self::from_f64(exact)
+        } else {
+            Self::from_log10(self.log10() + n as f64 * base.log10())
+        }
+    }
+
+    /// `self^power`, exact while the amount and the result stay small.
+    pub fn powf(self, power: f64) -> Self {
+        let exact = self.to_f64().powf(power);
+        if exact.is_finite() && exact < EXACT && self.exponent == 0 {
+            Self::from_f64(exact)
+        } else {
+            Self::from_log10(self.log10() * power)
+        }
+    }
+
+    /// `None` if `other` is more than `self`.
+    pub fn checked_sub(self, other: Self) -> Option<Self> {
+        (self >= other).then(|| self.saturating_sub(other))
+    }
+
+    pub fn saturating_sub(self, other: Self) -> Self {
+        if other >= self {
+            return Self::ZERO;
+        }
+        Self::normalize(
+            self.mantissa - other.aligned_to(self.exponent),
+            self.exponent as i64,
+        )
+    }
+
+    /// The mantissa of `self` expressed with the larger `exponent`.
+    fn aligned_to(self, exponent: u32) -> f64 {
+        self.mantissa * 10f64.powi(-((exponent - self.exponent).min(400) as i32))
+    }
+}
+
+impl Add for Currency {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        let (big, small) = if self >= other {
+            (self, other)
+        } else {
+            (other, self)
+        };
+        Self::normalize(
+            big.mantissa + small.aligned_to(big.exponent),
+            big.exponent as i64,
+        )
+    }
+}
+
+impl AddAssign for Currency {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl Sum for Currency {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::ZERO, Add::add)
+    }
+}
+
+impl From<u32> for Currency {
+    fn from(amount: u32) -> Self {
+        Self::from_f64(amount as f64)
+    }
+}
+
+impl From<f32> for Currency {
+    fn from(amount: f32) -> Self {
+        Self::from_f64(amount as f64)
+    }
+}
+
+impl Eq for Currency {}
+
+impl PartialOrd for Currency {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Currency {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // normalized, a larger exponent always means a larger amount
+        self.exponent
+            .cmp(&other.exponent)
+            .then(self.mantissa.total_cmp(&other.mantissa))
+    }
+}
+
+/// In the default [`NumberFormat`], for logs.
+impl fmt::Display for Currency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(&NumberFormat::default().format(*self))
+    }
+}
+
+const SETTINGS_KEY: &str = "number_format";
+
+/// How amounts of a thousand and more are written. Smaller ones are written
+/// out, with one decimal if they have a fraction.
+#[derive(Resource, Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NumberFormat {
+    /// 1.2K, 3.4M, 5.6B, then scientific past the last suffix.
+    #[default]
+    Suffix,
+    /// 1.2e3, 3.4e6, 5.6e12
+    Scientific,
+    /// Exponents in steps of three: 1.2e3, 34.5e6, 5.6e12
+    Engineering,
+}
+
+/// Thousand, million, billion and so on, up to 10^33.
+const SUFFIXES: [&str; 11] = ["", "K", "M", "B", "T", "Qa", "Qi", "Sx", "Sp", "Oc", "No"];
+
+impl NumberFormat {
+    /// The stored format, the default if there is none.
+    fn load() -> Self {
+        let Some(contents) = storage::load(SETTINGS_KEY) else {
+            return Self::default();
+        };
+        ron::from_str(&contents).unwrap_or_else(|error| {
+            warn!("Ignoring unreadable number format: {error}");
+            Self::default()
+        })
+    }
+
+    pub fn store(&self) {
+        let result = ron::ser::to_string(self)
+            .map_err(std::io::Error::other)
+            .and_then(|contents| storage::save(SETTINGS_KEY, &contents));
+        if let Err(error) = result {
+            error!("Could not save number format: {error}");
+        }
+    }
+
+    pub fn next(self) -> Self {
+        match self {
+            Self::Suffix => Self::Scientific,
+            Self::Scientific => Self::Engineering,
+            Self::Engineering => Self::Suffix,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Suffix => "Suffixes",
+            Self::Scientific => "Scientific",
+            Self::Engineering => "Engineering",
+        }
+    }
+
+    pub fn format(self, amount: impl Into<Currency>) -> String {
+        let amount = amount.into();
+        let small = amount.to_f64();
+        // 999.96 would round up to 1000.0, it is written as 1.0K instead
+        if small < 999.95 {
+            return if small.fract() == 0.0 {
+                format!("{small}")
+            } else {
+                format!("{small:.1}")
+            };
+        }
+        let log10 = amount.log10();
+        // the power of ten the mantissa is shown against
+        let step = match self {
+            Self::Suffix if log10 < 3.0 * SUFFIXES.len() as f64 => 3,
+            Self::Suffix | Self::Scientific => 1,
+            Self::Engineering => 3,
+        };
+        let mut power = (log10.floor() as i64).div_euclid(step) * step;
+        // dividing is exact where going through the logarithm is a hair off
+        let scaled = if small.is_finite() {
+            small / 10f64.powi(power as i32)
+        } else {
+            10f64.powf(log10 - power as f64)
+        };
+        let mut mantissa = (scaled * 10.0).round() / 10.0;
+        // 999.96K rounds up to 1000.0K, show it as 1.0M
+        if mantissa >= 10f64.powi(step as i32) {
+            mantissa /= 10f64.powi(step as i32);
+            power += step;
+        }
+        match self {
+            Self::Suffix if step == 3 => {
+                let suffix = SUFFIXES.get((power / 3) as usize).copied();
+                match suffix {
+                    Some(suffix) => format!("{mantissa:.1}{suffix}"),
+                    None => format!("{mantissa:.1}e{power}"),
+                }
+            }
+            _ => format!("{mantissa:.1}e{power}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn log10_close(amount: Currency, log10: f64) -> bool {
+        (amount.log10() - log10).abs() < 1e-9
+    }
+
+    #[test]
+    fn normalize_keeps_the_mantissa_in_range() {
+        assert_eq!(Currency::from_f64(-1.0), Currency::ZERO);
+        assert_eq!(Currency::from_f64(f64::NAN), Currency::ZERO);
+        assert_eq!(Currency::from_f64(f64::INFINITY), Currency::MAX);
+        // exact below 10^15
+        assert_eq!(Currency::from_f64(123.0).to_f64(), 123.0);
+        assert_eq!(Currency::from_f64(EXACT - 1.0).to_f64(), EXACT - 1.0);
+        let big = Currency::from_f64(1e20);
+        assert!(big.mantissa < EXACT && big.mantissa >= EXACT / 10.0);
+        assert_eq!(big.exponent, 6);
+        assert!(log10_close(big, 20.0));
+    }
+
+    #[test]
+    fn add_across_exponents() {
+        let big = Currency::from_f64(1e20);
+        // too small to change the mantissa
+        assert_eq!(big + Currency::from(1u32), big);
+        assert!(log10_close(big + big, 2e20f64.log10()));
+        assert!(log10_close(Currency::from(1u32) + big, 20.0));
+        let mut sum = Currency::ZERO;
+        for _ in 0..1000 {
+            sum += Currency::from(7u32);
+        }
+        assert_eq!(sum, Currency::from(7000u32));
+        assert_eq!(Currency::MAX + Currency::MAX, Currency::MAX);
+    }
+
+    #[test]
+    fn subtract_across_exponents() {
+        let big = Currency::from_f64(1e20);
+        let small = Currency::from_f64(1e19);
+        assert!(log10_close(big.saturating_sub(small), 9e19f64.log10()));
+        assert_eq!(small.saturating_sub(big), Currency::ZERO);
+        assert_eq!(small.checked_sub(big), None);
+        assert_eq!(
+            Currency::from(10u32).checked_sub(Currency::from(4u32)),
+            Some(Currency::from(6u32))
+        );
+        assert_eq!(big.checked_sub(big), Some(Currency::ZERO));
+    }
+
+    #[test]
+    fn from_log10() {
+        assert_eq!(Currency::from_log10(3.0), Currency::from(1000u32));
+        assert_eq!(Currency::from_log10(f64::NAN), Currency::ZERO);
+        assert!(log10_close(Currency::from_log10(1000.5), 1000.5));
+        assert!(Currency::from_log10(1000.5).to_f64().is_infinite());
+        assert_eq!(Currency::from_log10(1e12), Currency::MAX);
+    }
+
+    #[test]
+    fn stored_round_trip() {
+        // saves before version 12 stored whole numbers
+        let old: Currency = ron::from_str("123").unwrap();
+        assert_eq!(old, Currency::from(123u32));
+        for amount in [
+            Currency::ZERO,
+            Currency::from_f64(2.5),
+            Currency::from_f64(1e20),
+            Currency::from_log10(1000.5),
+            Currency::MAX,
+        ] {
+            let stored = ron::to_string(&amount).unwrap();
+            assert_eq!(
+                ron::from_str::<Currency>(&stored).unwrap(),
+                amount,
+                "{stored}"
+            );
+        }
+    }
+
+    #[test]
+    fn format_small_amounts() {
+        let format = NumberFormat::Suffix;
+        assert_eq!(format.format(0u32), "0");
+        assert_eq!(format.format(999u32), "999");
+        assert_eq!(format.format(2.5f32), "2.5");
+        assert_eq!(format.format(Currency::from_f64(999.94)), "999.9");
+    }
+
+    #[test]
+    fn format_rounds_up_to_the_next_unit() {
+        let format = NumberFormat::Suffix;
+        assert_eq!(format.format(Currency::from_f64(999.95)), "1.0K");
+        assert_eq!(format.format(Currency::from_f64(999_950.0)), "1.0M");
+        assert_eq!(format.format(1_234_567u32), "1.2M");
+        assert_eq!(
+            NumberFormat::Scientific.format(Currency::from_f64(9.96e12)),
+            "1.0e13"
+        );
+        assert_eq!(
+            NumberFormat::Engineering.format(Currency::from_f64(999_960.0)),
+            "1.0e6"
+        );
+    }
+
+    #[test]
+    fn format_past_the_last_suffix() {
+        assert_eq!(
+            NumberFormat::Suffix.format(Currency::from_f64(9.99e32)),
+            "999.0No"
+        );
+        assert_eq!(
+            NumberFormat::Suffix.format(Currency::from_f64(1e33)),
+            "1.0e33"
+        );
+        assert_eq!(
+            NumberFormat::Suffix.format(Currency::from_log10(1000.5)),
+            "3.2e1000"
+        );
+        assert_eq!(NumberFormat::Scientific.format(12_345u32), "1.2e4");
+        assert_eq!(NumberFormat::Engineering.format(12_345u32), "12.3e3");
+    }
+}
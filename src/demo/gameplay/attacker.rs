@@ -1,19 +1,25 @@
-use bevy::color::palettes::{css::*, tailwind::*};
+use std::time::Duration;
+
+use bevy::color::palettes::{
+    css::{BLACK, WHITE},
+    tailwind::*,
+};
 use rand::seq::IndexedRandom;
+use serde::{Deserialize, Serialize};
 
 use crate::{
     CursorEvents,
     audio::sound_effect,
-    demo::{
-        GAME_AREA, PlayerStats,
-        gameplay::{damage::LIGHTING_RANGE, dust::Dust},
-    },
+    demo::{GameArea, PlayerStats, can_draw_gizmos},
     prelude::*,
 };
 
 use super::{
-    damage::{DamageType, generate_damage},
-    power::Power,
+    damage::{Damage, DamageType, generate_damage},
+    dust::update_dust_grid,
+    power::{EnergySpent, Power},
+    spatial::DustGrid,
+    targeting::{TargetingStrategy, cycle_strategy, strategy_label},
 };
 
 pub(super) fn plugin(app: &mut App) {
@@ -21,21 +27,31 @@ pub(super) fn plugin(app: &mut App) {
         LoadingStateConfig::new(AssetsState::Loading).load_collection::<AttackerAssets>(),
     );
 
-    app.add_systems(Update, tick_attacker_timer.in_set(AppSystems::TickTimers));
     app.add_systems(
         Update,
-        (
-            charge_attacker,
-            update_attacker_color,
-            attack_dust.run_if(resource_exists::<AttackerAssets>),
-        )
+        (sync_attack_interval, tick_attacker_timer)
             .chain()
+            .in_set(AppSystems::TickTimers),
+    );
+    app.add_systems(
+        Update,
+        (charge_attacker, update_attacker_color, attack_dust)
+            .chain()
+            .after(update_dust_grid)
             .in_set(AppSystems::Update),
     );
+    app.add_systems(Update, draw_attacker_range.run_if(can_draw_gizmos));
 
     app.add_observer(
-        |_: Trigger<SpawnAttacker>, mut commands: Commands, mut entropy: GlobalEntropy<WyRand>| {
-            let attacker = commands.spawn(attacker(Vec2::ZERO, 1.0, entropy.fork_rng()));
+        |t: Trigger<SpawnAttacker>, mut commands: Commands, mut entropy: GlobalEntropy<WyRand>| {
+            let stats = t.stats.unwrap_or_else(|| AttackerStats::new(t.damage_type));
+            let attacker = commands.spawn(attacker(
+                t.pos,
+                stats,
+                t.damage_type,
+                t.strategy,
+                entropy.fork_rng(),
+            ));
             setup_cursor_icon(attacker);
         },
     );
@@ -43,13 +59,104 @@ pub(super) fn plugin(app: &mut App) {
 
 #[derive(Component, Reflect, Debug)]
 #[reflect(Component)]
-struct Attacker {
+pub struct Attacker {
     timer: Timer,
     fully_charged: bool,
+    damage_type: DamageType,
 }
 
-#[derive(Event, Debug, Clone)]
-pub struct SpawnAttacker;
+impl Attacker {
+    pub fn damage_type(&self) -> DamageType {
+        self.damage_type
+    }
+}
+
+#[derive(Event, Debug, Clone, Default)]
+pub struct SpawnAttacker {
+    pub pos: Vec2,
+    pub damage_type: DamageType,
+    pub strategy: TargetingStrategy,
+    /// `None` for the base stats of the damage type.
+    pub stats: Option<AttackerStats>,
+}
+
+/// Marks an attacker under the cursor, its range is drawn.
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+struct Hovered;
+
+/// Marks an attacker being dragged, its range is drawn.
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+struct Dragged;
+
+/// Energy of a discharge before the global multiplier.
+pub const BASE_ATTACK_ENERGY: f32 = 5.0;
+const BASE_ATTACK_INTERVAL: f32 = 1.0;
+/// No limit, lightning hops on for as long as its energy lasts.
+const BASE_CHAIN_LIMIT: u32 = u32::MAX;
+
+/// Stats of one discharge point, upgraded individually. Global Research Lab
+/// upgrades multiply on top of these.
+#[derive(Component, Reflect, Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[reflect(Component)]
+pub struct AttackerStats {
+    /// Energy per discharge, also the power it takes to charge.
+    pub energy: f32,
+    /// Seconds between discharges.
+    pub interval: f32,
+    /// Reach of the first hit, before the global multiplier. Lightning hops
+    /// reach a bit less each.
+    pub range: f32,
+    /// How many times lightning may jump on after the first hit, `u32::MAX`
+    /// for no limit.
+    pub chain_limit: u32,
+    /// Number of upgrades bought for this discharge point.
+    pub upgrades: u32,
+}
+
+/// A stat of [`AttackerStats`] that can be upgraded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AttackerStat {
+    Energy,
+    Interval,
+    Range,
+    ChainLimit,
+}
+
+impl AttackerStat {
+    pub const ALL: [Self; 4] = [Self::Energy, Self::Interval, Self::Range, Self::ChainLimit];
+}
+
+impl AttackerStats {
+    pub fn new(damage_type: DamageType) -> Self {
+        Self {
+            energy: BASE_ATTACK_ENERGY,
+            interval: BASE_ATTACK_INTERVAL,
+            range: damage_type.range(),
+            chain_limit: BASE_CHAIN_LIMIT,
+            upgrades: 0,
+        }
+    }
+
+    /// These stats with one more level of `stat`.
+    pub fn upgraded(&self, stat: AttackerStat) -> Self {
+        let mut stats = *self;
+        match stat {
+            AttackerStat::Energy => stats.energy *= 1.25,
+            AttackerStat::Interval => stats.interval = (stats.interval * 0.9).max(0.2),
+            AttackerStat::Range => stats.range += 10.0,
+            AttackerStat::ChainLimit => stats.chain_limit = stats.chain_limit.saturating_add(1),
+        }
+        stats.upgrades += 1;
+        stats
+    }
+
+    /// Whether lightning stops hopping before its energy runs out.
+    pub fn has_chain_limit(&self) -> bool {
+        self.chain_limit < u32::MAX
+    }
+}
 
 #[derive(Resource, Reflect, Debug, Default, AssetCollection)]
 #[reflect(Resource)]
@@ -66,32 +173,48 @@ struct AttackerAssets {
     pub steps: Vec<Handle<AudioSource>>,
 }
 
-pub fn attacker(pos: Vec2, attack_interval: f32, entropy: Entropy<WyRand>) -> impl Bundle {
+pub fn attacker(
+    pos: Vec2,
+    stats: AttackerStats,
+    damage_type: DamageType,
+    strategy: TargetingStrategy,
+    entropy: Entropy<WyRand>,
+) -> impl Bundle {
     (
-        Name::new("Attacker"),
+        Name::new(format!("{damage_type:?} Attacker")),
         Attacker {
-            timer: Timer::from_seconds(attack_interval, TimerMode::Once),
+            timer: Timer::from_seconds(stats.interval, TimerMode::Once),
             fully_charged: false,
+            damage_type,
         },
         entropy,
         Transform::from_translation(pos.extend(1.0)),
         StateScoped(Screen::Gameplay),
-        Sprite::from_color(RED, Vec2::new(16.0, 16.0)),
+        Sprite::from_color(damage_type.color(), Vec2::new(16.0, 16.0)),
         Pickable::default(),
+        stats,
+        strategy,
+        children![strategy_label(strategy)],
     )
 }
 
-fn update_attacker_color(attacker: Query<(&Transform, &Attacker, &mut Sprite)>) {
+fn update_attacker_color(
+    attacker: Query<(&Transform, &Attacker, &mut Sprite)>,
+    area: Res<GameArea>,
+) {
     for (transform, attacker, mut sprite) in attacker {
         // if attacker out of bounds, set color to BLACK
-        if !GAME_AREA.contains(transform.translation.truncate()) {
+        if !area.contains(transform.translation.truncate()) {
             sprite.color = BLACK.into();
             continue;
         }
-        // mix color based on timer progress, from dark red to red
+        // mix color based on timer progress, from dark to the damage type's color
         let progress = attacker.timer.fraction();
         let progress = map_range(progress, 0.0..1.0, 0.5..1.0);
-        sprite.color = RED.mix(&BLACK, 1.0 - progress).into();
+        sprite.color = attacker
+            .damage_type
+            .color()
+            .mix(&BLACK.into(), 1.0 - progress);
         // if fully charged, set color to BLUE
         if attacker.fully_charged {
             sprite.color = YELLOW_300.into();
@@ -99,6 +222,14 @@ fn update_attacker_color(attacker: Query<(&Transform, &Attacker, &mut Sprite)>)
     }
 }
 
+fn sync_attack_interval(attackers: Query<(&mut Attacker, &AttackerStats), Changed<AttackerStats>>) {
+    for (mut attacker, stats) in attackers {
+        attacker
+            .timer
+            .set_duration(Duration::from_secs_f32(stats.interval));
+    }
+}
+
 fn tick_attacker_timer(query: Query<&mut Attacker>, time: Res<Time>) {
     for mut attacker in query {
         attacker.timer.tick(time.delta());
@@ -106,66 +237,80 @@ fn tick_attacker_timer(query: Query<&mut Attacker>, time: Res<Time>) {
 }
 
 fn charge_attacker(
-    mut attackers: Query<&mut Attacker>,
+    mut attackers: Query<(&mut Attacker, &AttackerStats)>,
     mut power: ResMut<Power>,
+    mut energy_spent: EventWriter<EnergySpent>,
     player_stats: Res<PlayerStats>,
     mut rng: GlobalEntropy<WyRand>,
 ) {
-    if power.current() < player_stats.attack_energy {
-        return; // Not enough power to charge any attacker
-    }
     let mut attackers = attackers
         .iter_mut()
-        .filter(|attacker| !attacker.fully_charged)
-        .filter(|attacker| attacker.timer.finished())
+        .filter(|(attacker, _)| !attacker.fully_charged)
+        .filter(|(attacker, _)| attacker.timer.finished())
         .collect::<Vec<_>>();
 
     // charge in random order
     attackers.shuffle(&mut rng);
-    for mut attacker in attackers {
-        if attacker.timer.finished() && power.current() >= player_stats.attack_energy {
-            power.consume(player_stats.attack_energy);
+    let mut spent = 0.0;
+    for (mut attacker, stats) in attackers {
+        let energy = stats.energy * player_stats.energy_multiplier;
+        if power.current() >= energy {
+            power.consume(energy);
+            spent += energy;
             attacker.fully_charged = true;
         }
     }
+    if spent > 0.0 {
+        energy_spent.write(EnergySpent(spent));
+    }
 }
 
 fn attack_dust(
     mut commands: Commands,
-    attacker: Query<(&mut Attacker, &mut Entropy<WyRand>, &Transform)>,
+    attacker: Query<(
+        &mut Attacker,
+        &AttackerStats,
+        &TargetingStrategy,
+        &mut Entropy<WyRand>,
+        &Transform,
+    )>,
     player_stats: Res<PlayerStats>,
-    attacker_assets: Res<AttackerAssets>,
-    dust: Query<&Transform, With<Dust>>,
+    attacker_assets: Option<Res<AttackerAssets>>,
+    dust_grid: Res<DustGrid>,
+    area: Res<GameArea>,
 ) {
-    for (mut attacker, mut entropy, attacker_trans) in attacker {
-        if !GAME_AREA.contains(attacker_trans.translation.truncate()) {
+    for (mut attacker, stats, strategy, mut entropy, attacker_trans) in attacker {
+        if !area.contains(attacker_trans.translation.truncate()) {
             continue; // Attacker is out of bounds
         }
         if !attacker.fully_charged {
             continue; // Attacker is not fully charged
         }
 
-        let has_dust = dust.iter().any(|dust_trans| {
-            let distance = dust_trans
-                .translation
-                .truncate()
-                .distance_squared(attacker_trans.translation.truncate());
-            distance < LIGHTING_RANGE * LIGHTING_RANGE
-        });
-        if !has_dust {
+        let range = stats.range * player_stats.range_multiplier;
+        if !dust_grid.any_within(attacker_trans.translation.truncate(), range) {
             continue; // No dust in range to attack
         }
 
         commands.spawn(generate_damage(
             attacker_trans.translation.truncate(),
-            player_stats.attack_energy,
-            DamageType::Lightning,
+            Damage {
+                amount: stats.energy * player_stats.energy_multiplier,
+                range,
+                hops_left: stats.chain_limit,
+                chained: 0,
+                previous: None,
+            },
+            attacker.damage_type,
+            *strategy,
             entropy.fork_rng(),
-            None,
-        ));
-        commands.spawn(sound_effect(
-            attacker_assets.steps.choose(&mut entropy).unwrap().clone(),
         ));
+        // no sound in the headless simulation
+        if let Some(attacker_assets) = &attacker_assets {
+            commands.spawn(sound_effect(
+                attacker_assets.steps.choose(&mut entropy).unwrap().clone(),
+            ));
+        }
 
         // Reset the attack timer and fully charged state
         attacker.timer.reset();
@@ -173,12 +318,34 @@ fn attack_dust(
     }
 }
 
+fn draw_attacker_range(
+    attackers: Query<(&Transform, &AttackerStats), Or<(With<Hovered>, With<Dragged>)>>,
+    player_stats: Res<PlayerStats>,
+    mut gizmos: Gizmos,
+) {
+    for (transform, stats) in attackers {
+        gizmos.circle_2d(
+            transform.translation.truncate(),
+            stats.range * player_stats.range_multiplier,
+            WHITE.with_alpha(0.5),
+        );
+    }
+}
+
 fn setup_cursor_icon(mut attacker: EntityCommands) {
-    attacker.observe(|_: Trigger<Pointer<Over>>, mut commands: Commands| {
+    attacker.observe(|t: Trigger<Pointer<Over>>, mut commands: Commands| {
         commands.trigger(CursorEvents::Over);
+        commands.entity(t.target()).insert(Hovered);
     });
-    attacker.observe(|_: Trigger<Pointer<Out>>, mut commands: Commands| {
+    attacker.observe(|t: Trigger<Pointer<Out>>, mut commands: Commands| {
         commands.trigger(CursorEvents::Out);
+        commands.entity(t.target()).remove::<Hovered>();
+    });
+    attacker.observe(|t: Trigger<Pointer<DragStart>>, mut commands: Commands| {
+        commands.entity(t.target()).insert(Dragged);
+    });
+    attacker.observe(|t: Trigger<Pointer<DragEnd>>, mut commands: Commands| {
+        commands.entity(t.target()).remove::<Dragged>();
     });
 
     attacker.observe(|_: Trigger<Pointer<Pressed>>, mut commands: Commands| {
@@ -188,6 +355,8 @@ fn setup_cursor_icon(mut attacker: EntityCommands) {
         commands.trigger(CursorEvents::Released);
     });
 
+    attacker.observe(cycle_strategy);
+
     attacker.observe(
         |t: Trigger<Pointer<Drag>>, mut attackers: Query<&mut Transform, With<Attacker>>| {
             if let Ok(mut transform) = attackers.get_mut(t.target()) {
@@ -1,148 +1,300 @@
 use bevy::color::palettes::css::*;
+use serde::{Deserialize, Serialize};
 
 use crate::{
+    demo::currency::NumberFormat,
     prelude::*,
-    visual_effect::{AttackLine, TempoEffect},
+    visual_effect::{AttackCircle, AttackLine, TempoEffect},
 };
 
-use super::{dust::Dust, health::Health};
+use super::{
+    dust::{Chilled, Dust, update_dust_grid},
+    health::Health,
+    spatial::DustGrid,
+    targeting::TargetingStrategy,
+};
 
 pub(super) fn plugin(app: &mut App) {
     app.add_event::<AttackDustEvent>();
     app.add_systems(
         Update,
-        (deal_damage, deal_attack_event).in_set(AppSystems::Update),
+        (deal_damage.after(update_dust_grid), deal_attack_event).in_set(AppSystems::Update),
     );
 }
 
-#[derive(Component, Reflect, Debug)]
+#[derive(Component, Reflect, Debug, Clone, Copy)]
 #[reflect(Component)]
-struct Damage {
-    amount: f32,
-    previous: Option<Entity>,
+pub struct Damage {
+    pub amount: f32,
+    /// How far from its position the damage reaches.
+    pub range: f32,
+    /// Lightning hops left after this one.
+    pub hops_left: u32,
+    /// Dust already hit by this lightning chain.
+    pub chained: u32,
+    pub previous: Option<Entity>,
 }
 
-#[derive(Component, Reflect, Debug, Clone, Copy)]
+/// How an attack spreads from the attacker to the dust.
+#[derive(
+    Component, Reflect, Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq,
+)]
 #[reflect(Component)]
 pub enum DamageType {
+    /// Chains from dust to dust, passing on the energy that is left.
+    #[default]
     Lightning,
+    /// Hits the nearest dust and splashes everything around it, weaker further out.
+    Fire,
+    /// Pierces every dust on a straight line towards the nearest dust.
+    Laser,
+    /// A weak pulse around the attacker that slows dust down.
+    Frost,
 }
 
-pub const LIGHTING_RANGE: f32 = 100.0;
+const LIGHTING_RANGE: f32 = 100.0;
+/// Each lightning hop reaches this much less far than the one before.
+const HOP_RANGE_DECAY: f32 = 0.85;
+const FIRE_SPLASH_RADIUS: f32 = 60.0;
+const LASER_RANGE: f32 = 150.0;
+const LASER_LENGTH: f32 = 300.0;
+const LASER_WIDTH: f32 = 12.0;
+/// Each dust the laser passes through weakens the beam by this factor.
+const LASER_PIERCE_FALLOFF: f32 = 0.8;
+const FROST_RADIUS: f32 = 80.0;
+const FROST_DAMAGE_RATIO: f32 = 0.3;
+const FROST_SLOW_FACTOR: f32 = 0.5;
+const FROST_SLOW_SECS: f32 = 2.0;
+
+impl DamageType {
+    /// Base range of an attacker of this type.
+    pub fn range(self) -> f32 {
+        match self {
+            Self::Lightning | Self::Fire => LIGHTING_RANGE,
+            Self::Laser => LASER_RANGE,
+            Self::Frost => FROST_RADIUS,
+        }
+    }
+
+    pub fn color(self) -> Color {
+        match self {
+            Self::Lightning => RED.into(),
+            Self::Fire => ORANGE_RED.into(),
+            Self::Laser => MAGENTA.into(),
+            Self::Frost => DEEP_SKY_BLUE.into(),
+        }
+    }
+}
 
 pub fn generate_damage(
     pos: Vec2,
-    amount: f32,
+    damage: Damage,
     damage_type: DamageType,
+    strategy: TargetingStrategy,
     entropy: Entropy<WyRand>,
-    previous: Option<Entity>,
 ) -> impl Bundle {
     (
-        Name::new("Lightning Damage"),
+        Name::new(format!("{damage_type:?} Damage")),
         Transform::from_translation(pos.extend(0.0)),
-        Damage { amount, previous },
+        damage,
         entropy,
         damage_type,
+        strategy,
     )
 }
 // This system processes damage entities and applies effects based on their type.
 // first, it checks for damage type
-// second, find dust that can deal damage to
-// third, if dust is found, send an event to attack dust
+// second, find the dust the damage type reaches, picked by the targeting strategy
+// third, deal damage and send an event per hit dust
 // fourth, despawn the damage entity
-// additionnally, the resumed energy is passed to the next damage entity which will be spawned by event
+// additionnally, lightning passes the resumed energy to the next damage entity which will be spawned by event
 fn deal_damage(
     mut commands: Commands,
     damages: Query<(
         Entity,
         &Damage,
         &DamageType,
+        &TargetingStrategy,
         &Transform,
         &mut Entropy<WyRand>,
     )>,
-    mut dust: Query<(Entity, &mut Health, &Transform), With<Dust>>,
-    // mut power: ResMut<Power>,
+    mut dust: Query<(&mut Health, &Transform, &Dust)>,
+    dust_grid: Res<DustGrid>,
 ) -> Result {
     let mut attacked_dust = vec![];
-    for (damage_entity, Damage { amount, previous }, damage_type, damage_transform, mut entropy) in
-        damages
+    for (
+        damage_entity,
+        Damage {
+            amount,
+            range,
+            hops_left,
+            chained,
+            previous,
+        },
+        damage_type,
+        strategy,
+        damage_transform,
+        mut entropy,
+    ) in damages
     {
+        // cleanup damage entity
+        commands.entity(damage_entity).despawn();
+
         if let Some(previous) = previous {
             attacked_dust.push(*previous);
         }
+        let damage_pos = damage_transform.translation.truncate();
+        // damage one dust, lightning `chain`s the energy that is left to the next one
+        let hit = |commands: &mut Commands,
+                   dust: &mut Query<(&mut Health, &Transform, &Dust)>,
+                   target: Entity,
+                   deal_amount: f32,
+                   chain: bool,
+                   entropy: &Entropy<WyRand>|
+         -> Result {
+            let (mut health, dust_transform, kind) = dust.get_mut(target)?;
+            // killed by an earlier hit this frame, it is despawned later
+            if !health.is_alive() {
+                return Ok(());
+            }
+            // resisted damage still spends the energy
+            let taken = kind.spec().damage_taken(*damage_type);
+            // immune dust can't be hurt and doesn't soak up the energy either
+            if taken <= 0.0 {
+                return Ok(());
+            }
+            let spent = deal_amount.clamp(0.0, health.current() * 1.2 / taken);
+            health.apply_damage(spent * taken);
+            commands.send_event(AttackDustEvent {
+                source: damage_pos,
+                target: dust_transform.translation.truncate(),
+                previous: target,
+                amount: spent * taken,
+                remaining_energy: if chain { *amount - spent } else { 0.0 },
+                range: *range,
+                hops_left: *hops_left,
+                hop: chained + 1,
+                damage_type: *damage_type,
+                strategy: *strategy,
+                entropy: entropy.clone(),
+            });
+            Ok(())
+        };
+
         match damage_type {
             DamageType::Lightning => {
-                // search for dust entities within a certain radius
-                // TODO: use a more efficient spatial partitioning method
-                let damage_pos = damage_transform.translation.truncate();
-
-                // find dust entities that are within LIGHTING_RANGE of the damage entity
-                let (nearest_dust, _, dust_pos) = dust
-                    .iter()
-                    .filter(|(_, _, transform)| {
-                        let distance = transform
-                            .translation
-                            .truncate()
-                            .distance_squared(damage_pos);
-                        distance < LIGHTING_RANGE * LIGHTING_RANGE // radius squared
-                    })
-                    .filter(|(e, _, _)| !attacked_dust.contains(e))
-                    .fold(
-                        (Entity::PLACEHOLDER, f32::INFINITY, Vec2::ZERO),
-                        |min, (entity, _, transform)| {
-                            let pos = transform.translation.truncate();
-                            let distance = pos.distance_squared(damage_pos);
-                            if distance < min.1 {
-                                (entity, distance, pos)
-                            } else {
-                                min
-                            }
-                        },
-                    );
-                if nearest_dust == Entity::PLACEHOLDER {
-                    // no dust found, dissipate
-                    commands.entity(damage_entity).despawn();
-                    continue;
-                }
-
-                let (_, mut health, _) = dust.get_mut(nearest_dust)?;
+                // pick a dust within range that hasn't been hit yet
+                let candidates = dust_grid
+                    .within(damage_pos, *range)
+                    .filter(|(e, _)| !attacked_dust.contains(e) && is_alive(&dust, *e));
+                let Some((nearest_dust, _)) = strategy.pick(damage_pos, candidates, &dust) else {
+                    continue; // no dust found, dissipate
+                };
                 // random the amount of damage to apply
                 let deal_amount = entropy.random_range((amount / 2.0)..=*amount);
-                let deal_amount = deal_amount.clamp(0.0, health.current() * 1.2);
-                health.apply_damage(deal_amount);
-
+                hit(
+                    &mut commands,
+                    &mut dust,
+                    nearest_dust,
+                    deal_amount,
+                    true,
+                    &entropy,
+                )?;
                 attacked_dust.push(nearest_dust);
-                commands.send_event(AttackDustEvent {
-                    source: damage_transform.translation.truncate(),
-                    target: dust_pos,
-                    previous: nearest_dust,
-                    amount: deal_amount,
-                    remaining_energy: *amount - deal_amount,
-                    damage_type: *damage_type,
-                    entropy: entropy.clone(),
-                });
+            }
+            DamageType::Fire => {
+                let candidates = dust_grid
+                    .within(damage_pos, *range)
+                    .filter(|(e, _)| is_alive(&dust, *e));
+                let Some((_, center)) = strategy.pick(damage_pos, candidates, &dust) else {
+                    continue;
+                };
+                // linear falloff from the full amount at the center to nothing at the edge
+                for (target, pos) in dust_grid.within(center, FIRE_SPLASH_RADIUS) {
+                    let falloff = 1.0 - pos.distance(center) / FIRE_SPLASH_RADIUS;
+                    hit(
+                        &mut commands,
+                        &mut dust,
+                        target,
+                        amount * falloff,
+                        false,
+                        &entropy,
+                    )?;
+                }
+                commands.spawn(area_effect(center, FIRE_SPLASH_RADIUS, *damage_type));
+            }
+            DamageType::Laser => {
+                let candidates = dust_grid
+                    .within(damage_pos, *range)
+                    .filter(|(e, _)| is_alive(&dust, *e));
+                let Some((_, aim)) = strategy.pick(damage_pos, candidates, &dust) else {
+                    continue;
+                };
+                let direction = (aim - damage_pos).normalize_or(Vec2::Y);
+                let end = damage_pos + direction * LASER_LENGTH;
+                let mut on_line = dust_grid
+                    .within(damage_pos, LASER_LENGTH)
+                    .filter_map(|(target, pos)| {
+                        let along = (pos - damage_pos).dot(direction);
+                        let across = (pos - damage_pos).perp_dot(direction).abs();
+                        (along >= 0.0 && across <= LASER_WIDTH).then_some((target, along))
+                    })
+                    .collect::<Vec<_>>();
+                on_line.sort_by(|(_, a), (_, b)| a.total_cmp(b));
+                let mut beam = *amount;
+                for (target, _) in on_line {
+                    hit(&mut commands, &mut dust, target, beam, false, &entropy)?;
+                    beam *= LASER_PIERCE_FALLOFF;
+                }
+                commands.spawn(line_effect(damage_pos, end, damage_type.color(), 0.15));
+            }
+            DamageType::Frost => {
+                for (target, _) in dust_grid.within(damage_pos, *range) {
+                    hit(
+                        &mut commands,
+                        &mut dust,
+                        target,
+                        amount * FROST_DAMAGE_RATIO,
+                        false,
+                        &entropy,
+                    )?;
+                    commands
+                        .entity(target)
+                        .try_insert(Chilled::new(FROST_SLOW_FACTOR, FROST_SLOW_SECS));
+                }
+                commands.spawn(area_effect(damage_pos, *range, *damage_type));
             }
         }
-        // cleanup damage entity
-        commands.entity(damage_entity).despawn();
     }
     Ok(())
 }
 
+/// Dust killed earlier in the frame is only despawned later, it can't be hit.
+fn is_alive(dust: &Query<(&mut Health, &Transform, &Dust)>, target: Entity) -> bool {
+    dust.get(target).is_ok_and(|(health, ..)| health.is_alive())
+}
+
+/// Sent for every dust hit by an attack.
 #[derive(Event, Debug)]
-struct AttackDustEvent {
+pub struct AttackDustEvent {
     source: Vec2,
     target: Vec2,
     previous: Entity,
-    amount: f32,
+    pub amount: f32,
     remaining_energy: f32,
-    damage_type: DamageType,
+    range: f32,
+    hops_left: u32,
+    /// Position of the hit dust in its lightning chain, 1 for the first.
+    pub hop: u32,
+    pub damage_type: DamageType,
+    strategy: TargetingStrategy,
     entropy: Entropy<WyRand>,
 }
 
 fn deal_attack_event(
     mut commands: Commands,
     mut event_reader: EventReader<AttackDustEvent>,
+    format: Res<NumberFormat>,
 ) -> Result {
     for &AttackDustEvent {
         source,
@@ -150,19 +302,33 @@ fn deal_attack_event(
         previous,
         amount,
         remaining_energy,
+        range,
+        hops_left,
+        hop,
         damage_type,
+        strategy,
         ref entropy,
     } in event_reader.read()
     {
-        commands.spawn(lightning_effect(target, source));
-        commands.spawn(damage_text(amount, target));
-        if remaining_energy >= 1.0 {
+        commands.spawn(hit_effect(target, damage_type));
+        // the area and beam effects already show where the other types came from
+        if damage_type == DamageType::Lightning {
+            commands.spawn(line_effect(source, target, Color::WHITE, 0.1));
+        }
+        commands.spawn(damage_text(&format.format(amount), target));
+        if remaining_energy >= 1.0 && hops_left > 0 {
             commands.spawn(generate_damage(
                 target,
-                remaining_energy,
+                Damage {
+                    amount: remaining_energy,
+                    range: range * HOP_RANGE_DECAY,
+                    hops_left: hops_left - 1,
+                    chained: hop,
+                    previous: Some(previous),
+                },
                 damage_type,
+                strategy,
                 entropy.clone(),
-                Some(previous),
             ));
         }
     }
@@ -170,26 +336,48 @@ fn deal_attack_event(
     Ok(())
 }
 
-fn lightning_effect(target: Vec2, source: Vec2) -> impl Bundle {
+fn hit_effect(target: Vec2, damage_type: DamageType) -> impl Bundle {
+    let color = match damage_type {
+        DamageType::Lightning => ORANGE.into(),
+        DamageType::Fire | DamageType::Laser | DamageType::Frost => damage_type.color(),
+    };
     (
-        Name::new("Lightning Effect"),
+        Name::new(format!("{damage_type:?} Effect")),
         StateScoped(Screen::Gameplay),
         TempoEffect::new(0.1),
         Transform::from_translation(target.extend(0.0)),
-        Sprite::from_color(ORANGE, Vec2::new(16.0, 16.0)),
-        AttackLine {
-            start: source,
-            end: target,
+        Sprite::from_color(color, Vec2::new(16.0, 16.0)),
+    )
+}
+
+fn line_effect(start: Vec2, end: Vec2, color: Color, duration: f32) -> impl Bundle {
+    (
+        Name::new("Attack Line"),
+        StateScoped(Screen::Gameplay),
+        TempoEffect::new(duration),
+        AttackLine { start, end, color },
+    )
+}
+
+fn area_effect(center: Vec2, radius: f32, damage_type: DamageType) -> impl Bundle {
+    (
+        Name::new(format!("{damage_type:?} Area")),
+        StateScoped(Screen::Gameplay),
+        TempoEffect::new(0.2),
+        AttackCircle {
+            center,
+            radius,
+            color: damage_type.color(),
         },
     )
 }
 
-fn damage_text(amount: f32, pos: Vec2) -> impl Bundle {
+fn damage_text(amount: &str, pos: Vec2) -> impl Bundle {
     (
         Name::new("Damage Text"),
         StateScoped(Screen::Gameplay),
         Transform::from_translation(pos.extend(0.0)),
-        Text2d::new(format!("-{:.1}", amount)),
+        Text2d::new(format!("-{amount}")),
         TempoEffect::new(0.5),
         TextFont::from_font_size(12.0),
         TextColor(RED.into()),
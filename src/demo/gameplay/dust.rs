@@ -1,17 +1,42 @@
+use std::f32::consts::TAU;
+
 use bevy::diagnostic::Diagnostics;
+use serde::{Deserialize, Serialize};
 
 use crate::{
     demo::{
-        GAME_AREA,
-        ui::{collect_rate::DUST_COLLECT_RATE_DIAGNOSTIC, inventory::Inventory},
+        GameArea,
+        currency::Currency,
+        ui::{
+            collect_rate::{DUST_COLLECT_RATE_DIAGNOSTIC, DUST_LEAK_RATE_DIAGNOSTIC},
+            inventory::{DustDataChanged, Inventory},
+        },
     },
     prelude::*,
 };
 
-use super::health::{Health, health_bar_and_ui};
+use super::{
+    health::{Health, health_bar_and_ui},
+    pollution::PollutionMeter,
+    power::Power,
+    spatial::{CELL_SIZE, DustGrid},
+    species::OnDeath,
+    wind::Wind,
+};
 
 pub(super) fn plugin(app: &mut App) {
-    app.add_systems(Update, (falling_dust,).in_set(AppSystems::Update));
+    app.add_event::<DustDestroyed>();
+    app.add_event::<DustEscaped>();
+    app.insert_resource(DustGrid::new(GameArea::default().0, CELL_SIZE));
+    app.add_systems(
+        Update,
+        resize_dust_grid.run_if(resource_changed::<GameArea>),
+    );
+    app.add_systems(Update, tick_chilled.in_set(AppSystems::TickTimers));
+    app.add_systems(
+        Update,
+        (regenerate_dust, (move_dust, update_dust_grid).chain()).in_set(AppSystems::Update),
+    );
     app.add_systems(
         Update,
         (cleanup_unalived_dust, despawn_dust)
@@ -20,12 +45,51 @@ pub(super) fn plugin(app: &mut App) {
     );
 }
 
-#[derive(Component, Reflect, Debug, Default)]
+/// The species of a dust, see [`Dust::spec`] for what sets them apart.
+#[derive(
+    Component,
+    Reflect,
+    Serialize,
+    Deserialize,
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+)]
 #[reflect(Component)]
-pub(super) enum Dust {
+pub enum Dust {
     #[default]
     Small,
+    /// Splits into small dust.
     Big,
+    /// Shrugs off most lightning.
+    Armored,
+    /// Drains power when it escapes.
+    Toxic,
+    /// Heals over time.
+    Regenerating,
+    /// Rare and worth a lot of dust data.
+    Golden,
+    /// Small and fast.
+    Spark,
+    /// A boss that splits into big dust, only spawned by
+    /// [dust events](super::dust_events).
+    Mega,
+}
+
+/// Sent for every dust destroyed by discharges, not for escaped dust.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct DustDestroyed(pub Dust);
+
+/// Sent for every dust that escaped at the bottom.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct DustEscaped {
+    /// Pollution it leaked.
+    pub leak: f32,
 }
 
 #[derive(Component, Reflect, Debug)]
@@ -34,13 +98,77 @@ struct Velocity {
     speed: f32,
 }
 
+/// How a dust moves around its path, on top of falling and drifting with the
+/// [`Wind`].
+#[derive(Component, Reflect, Debug, Clone, Copy, Default)]
+#[reflect(Component)]
+pub(super) enum Movement {
+    #[default]
+    Fall,
+    /// Sways side to side.
+    Wobble {
+        amplitude: f32,
+        frequency: f32,
+        phase: f32,
+    },
+    /// Circles around its path.
+    Swirl {
+        radius: f32,
+        angular_speed: f32,
+        phase: f32,
+    },
+}
+
+impl Movement {
+    /// Offset from the path after `elapsed` seconds.
+    fn offset(&self, elapsed: f32) -> Vec2 {
+        match *self {
+            Self::Fall => Vec2::ZERO,
+            Self::Wobble {
+                amplitude,
+                frequency,
+                phase,
+            } => Vec2::new(amplitude * ops::sin(TAU * frequency * elapsed + phase), 0.0),
+            Self::Swirl {
+                radius,
+                angular_speed,
+                phase,
+            } => Vec2::from_angle(angular_speed * elapsed + phase) * radius,
+        }
+    }
+}
+
+/// Where the dust would be without its [`Movement`].
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+struct DustPath {
+    anchor: Vec2,
+    elapsed: f32,
+    /// How strongly the wind pushes this dust.
+    drift: f32,
+}
+
+/// Slows dust down until the timer runs out.
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+pub(super) struct Chilled {
+    factor: f32,
+    timer: Timer,
+}
+
+impl Chilled {
+    pub(super) fn new(factor: f32, duration: f32) -> Self {
+        Self {
+            factor,
+            timer: Timer::from_seconds(duration, TimerMode::Once),
+        }
+    }
+}
+
 pub const DUST_HEALTH: f32 = 5.0;
 
-pub fn dust(pos: Vec2, speed: f32, dust: Dust) -> impl Bundle {
-    let (health, color, size) = match dust {
-        Dust::Small => (DUST_HEALTH, Color::WHITE, Vec2::new(16.0, 16.0)),
-        Dust::Big => (DUST_HEALTH * 2.0, Color::BLACK, Vec2::new(20.0, 20.0)),
-    };
+pub fn dust(pos: Vec2, speed: f32, dust: Dust, movement: Movement) -> impl Bundle {
+    let spec = dust.spec();
 
     (
         Name::new("Dust"),
@@ -48,17 +176,86 @@ pub fn dust(pos: Vec2, speed: f32, dust: Dust) -> impl Bundle {
         Transform::from_translation(pos.extend(0.0)),
         StateScoped(Screen::Gameplay),
         Velocity { speed },
+        movement,
+        DustPath {
+            anchor: pos,
+            elapsed: 0.0,
+            drift: spec.drift,
+        },
         Sprite::from_color(
-            color.with_alpha(map_range(speed, 80.0..120.0, 0.4..1.0)),
-            size,
+            spec.color
+                .with_alpha(map_range(speed, spec.speed, 0.4..1.0).clamp(0.4, 1.0)),
+            Vec2::splat(spec.size),
+        ),
+        health_bar_and_ui(
+            spec.health,
+            Vec2::new(0.0, spec.size / 2.0 + 3.0),
+            Vec2::new(20.0, 4.0),
         ),
-        health_bar_and_ui(health, Vec2::new(0.0, 11.0), Vec2::new(20.0, 4.0)),
     )
 }
 
-fn falling_dust(query: Query<(&mut Transform, &Velocity), With<Dust>>, time: Res<Time>) {
-    for (mut transform, velocity) in query {
-        transform.translation.y -= velocity.speed * time.delta_secs();
+fn move_dust(
+    query: Query<
+        (
+            &mut Transform,
+            &mut DustPath,
+            &Velocity,
+            &Movement,
+            Option<&Chilled>,
+        ),
+        With<Dust>,
+    >,
+    wind: Res<Wind>,
+    area: Res<GameArea>,
+    time: Res<Time>,
+) {
+    let width = area.width();
+    for (mut transform, mut path, velocity, movement, chilled) in query {
+        let factor = chilled.map_or(1.0, |chilled| chilled.factor);
+        let dt = time.delta_secs() * factor;
+        path.elapsed += dt;
+        path.anchor.y -= velocity.speed * dt;
+        path.anchor.x += wind.speed() * path.drift * dt;
+        // dust blown out of a side comes back in on the other side
+        path.anchor.x = (path.anchor.x - area.min.x).rem_euclid(width) + area.min.x;
+
+        let pos = path.anchor + movement.offset(path.elapsed) - movement.offset(0.0);
+        let pos = pos.with_x(pos.x.clamp(area.min.x, area.max.x));
+        transform.translation = pos.extend(transform.translation.z);
+    }
+}
+
+fn regenerate_dust(query: Query<(&mut Health, &Dust)>, time: Res<Time>) {
+    for (mut health, dust) in query {
+        let regen = dust.spec().regen;
+        if regen > 0.0 && health.is_alive() {
+            health.heal(regen * time.delta_secs());
+        }
+    }
+}
+
+fn tick_chilled(mut commands: Commands, query: Query<(Entity, &mut Chilled)>, time: Res<Time>) {
+    for (entity, mut chilled) in query {
+        if chilled.timer.tick(time.delta()).finished() {
+            commands.entity(entity).remove::<Chilled>();
+        }
+    }
+}
+
+/// Cover a new level's arena with the dust grid.
+fn resize_dust_grid(mut commands: Commands, area: Res<GameArea>) {
+    commands.insert_resource(DustGrid::new(area.0, CELL_SIZE));
+}
+
+/// Rebuild the dust grid from scratch, after dust has moved this frame.
+pub(super) fn update_dust_grid(
+    mut grid: ResMut<DustGrid>,
+    query: Query<(Entity, &Transform), With<Dust>>,
+) {
+    grid.clear();
+    for (entity, transform) in query {
+        grid.insert(entity, transform.translation.truncate());
     }
 }
 
@@ -66,52 +263,81 @@ fn cleanup_unalived_dust(
     mut commands: Commands,
     query: Query<(Entity, &Health, &Transform, &Dust)>,
     mut inventory: ResMut<Inventory>,
+    mut destroyed: EventWriter<DustDestroyed>,
+    mut data_changed: EventWriter<DustDataChanged>,
     mut rng: GlobalEntropy<WyRand>,
     mut diagnostic: Diagnostics,
+    area: Res<GameArea>,
     time: Res<Time>,
 ) -> Result {
     let mut all_dust_data = 0;
     for (entity, health, transform, dust_ty) in query {
         if !health.is_alive() {
-            match dust_ty {
-                Dust::Small => {
-                    all_dust_data += 1; // Small dust equals 1 dust data
-                }
-                Dust::Big => {
-                    all_dust_data += 2; // Big dust equals 2 dust data
-                    let pos = transform.translation.truncate();
-                    // despawn big dust, gen small dusts
-                    let max_range = 100.0;
-                    // 95% confidence interval for normal distribution
-                    let distr = rand_distr::Normal::new(0.0, max_range / 2.45)?;
-                    // spawn 4 small dusts around the position of big dust
-                    for _ in 0..4 {
-                        let x = distr.sample(&mut rng);
-                        let y = distr.sample(&mut rng);
-                        let diff = Vec2::new(x, y).clamp_length_max(max_range);
-                        let speed = rng.random_range(80.0..120.0);
-                        commands.spawn(dust(
-                            (pos + diff).clamp(GAME_AREA.min, GAME_AREA.max),
-                            speed,
-                            Dust::Small,
-                        ));
-                    }
+            let spec = dust_ty.spec();
+            all_dust_data += spec.reward;
+            destroyed.write(DustDestroyed(*dust_ty));
+            if let OnDeath::Split {
+                into,
+                count,
+                spread,
+            } = spec.on_death
+            {
+                let pos = transform.translation.truncate();
+                // 95% confidence interval for normal distribution
+                let distr = rand_distr::Normal::new(0.0, spread / 2.45)?;
+                // scatter the pieces around the dead dust
+                for _ in 0..count {
+                    let x = distr.sample(&mut rng);
+                    let y = distr.sample(&mut rng);
+                    let diff = Vec2::new(x, y).clamp_length_max(spread);
+                    let speed = rng.random_range(into.spec().speed);
+                    commands.spawn(dust(
+                        (pos + diff).clamp(area.min, area.max),
+                        speed,
+                        into,
+                        Movement::Fall,
+                    ));
                 }
             }
             commands.entity(entity).despawn();
         }
     }
-    inventory.dust_data += all_dust_data;
+    let earned = Currency::from(all_dust_data);
+    inventory.dust_data += earned;
+    inventory.collected += earned;
+    if all_dust_data > 0 {
+        data_changed.write(DustDataChanged::Earned(earned));
+    }
     diagnostic.add_measurement(&DUST_COLLECT_RATE_DIAGNOSTIC, || {
         all_dust_data as f64 / time.delta_secs() as f64
     });
     Ok(())
 }
 
-fn despawn_dust(mut commands: Commands, query: Query<(Entity, &Transform), With<Dust>>) {
-    for (entity, transform) in query.iter() {
-        if transform.translation.y < GAME_AREA.min.y {
+/// Despawn dust that escaped at the bottom, leaking its pollution. Dust never
+/// leaves sideways, see [`move_dust`].
+fn despawn_dust(
+    mut commands: Commands,
+    query: Query<(Entity, &Transform, &Dust)>,
+    mut power: ResMut<Power>,
+    mut meter: ResMut<PollutionMeter>,
+    mut escaped: EventWriter<DustEscaped>,
+    mut diagnostic: Diagnostics,
+    area: Res<GameArea>,
+    time: Res<Time>,
+) {
+    let mut leaked = 0.0;
+    for (entity, transform, dust) in query.iter() {
+        if transform.translation.y < area.min.y {
+            let spec = dust.spec();
+            power.consume(spec.escape_drain);
+            leaked += spec.leak;
+            escaped.write(DustEscaped { leak: spec.leak });
             commands.entity(entity).despawn();
         }
     }
+    meter.leak(leaked);
+    diagnostic.add_measurement(&DUST_LEAK_RATE_DIAGNOSTIC, || {
+        leaked as f64 / time.delta_secs() as f64
+    });
 }
@@ -0,0 +1,274 @@
+//! Dust storms, bosses and calm periods layered on top of the dust spawner.
+//!
+//! Every [`DustEvent`] is declared in one table, see [`DustEvent::spec`]. Some
+//! come back on a fixed schedule, the others are rolled at random intervals.
+//! Only one event runs at a time, a scheduled event that comes due during
+//! another one waits for it to end.
+
+use std::ops::Range;
+
+use bevy::color::palettes::css::{BLACK, WHITE};
+
+use crate::{demo::GameArea, prelude::*};
+
+use super::dust::{Dust, Movement, dust};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<DustEvents>();
+    app.add_systems(OnEnter(Screen::Gameplay), reset_dust_events);
+    app.add_systems(
+        Update,
+        tick_dust_event_timers.in_set(AppSystems::TickTimers),
+    );
+    app.add_systems(
+        Update,
+        run_dust_events
+            .in_set(AppSystems::Update)
+            .run_if(in_state(Screen::Gameplay)),
+    );
+    app.add_systems(
+        Update,
+        update_dust_event_banner.run_if(in_state(Screen::Gameplay)),
+    );
+
+    app.add_observer(spawn_boss);
+}
+
+/// Seconds between rolls for a random event.
+const RANDOM_EVENT_INTERVAL: Range<f32> = 60.0..120.0;
+
+#[derive(Reflect, Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum DustEvent {
+    Storm,
+    Boss,
+    Calm,
+}
+
+pub(super) struct DustEventSpec {
+    /// Shown in the banner while the event runs.
+    pub banner: &'static str,
+    pub duration: f32,
+    /// Multiplies the dust spawner's rate.
+    pub spawn_multiplier: f32,
+    /// Spawned once when the event starts.
+    pub boss: Option<Dust>,
+    /// Seconds into the run of the first occurrence and between the next ones,
+    /// `None` for events that only happen at random.
+    pub schedule: Option<(f32, f32)>,
+    /// Relative chance to be picked at random, 0 for scheduled only.
+    pub random_weight: f32,
+}
+
+impl DustEvent {
+    pub(super) const ALL: [Self; 3] = [Self::Storm, Self::Boss, Self::Calm];
+
+    pub(super) fn spec(&self) -> DustEventSpec {
+        let base = DustEventSpec {
+            banner: "",
+            duration: 20.0,
+            spawn_multiplier: 1.0,
+            boss: None,
+            schedule: None,
+            random_weight: 0.0,
+        };
+        match self {
+            Self::Storm => DustEventSpec {
+                banner: "Dust Storm!",
+                spawn_multiplier: 3.0,
+                random_weight: 2.0,
+                ..base
+            },
+            Self::Boss => DustEventSpec {
+                banner: "Mega Dust incoming!",
+                duration: 10.0,
+                boss: Some(Dust::Mega),
+                schedule: Some((240.0, 300.0)),
+                ..base
+            },
+            Self::Calm => DustEventSpec {
+                banner: "Calm skies",
+                duration: 30.0,
+                spawn_multiplier: 0.3,
+                random_weight: 1.0,
+                ..base
+            },
+        }
+    }
+}
+
+/// Announce that `0` started, observed to spawn its boss.
+#[derive(Event, Debug)]
+pub(super) struct DustEventStarted(pub DustEvent);
+
+#[derive(Resource, Reflect, Debug)]
+#[reflect(Resource)]
+pub(super) struct DustEvents {
+    active: Option<(DustEvent, Timer)>,
+    /// Scheduled events that came due during another event.
+    queued: Vec<DustEvent>,
+    random_timer: Timer,
+    schedule_timers: Vec<(DustEvent, Timer)>,
+}
+
+impl Default for DustEvents {
+    fn default() -> Self {
+        Self {
+            active: None,
+            queued: Vec::new(),
+            random_timer: Timer::from_seconds(RANDOM_EVENT_INTERVAL.end, TimerMode::Once),
+            schedule_timers: DustEvent::ALL
+                .into_iter()
+                .filter_map(|event| {
+                    let (first, _) = event.spec().schedule?;
+                    Some((event, Timer::from_seconds(first, TimerMode::Once)))
+                })
+                .collect(),
+        }
+    }
+}
+
+impl DustEvents {
+    /// The running event and its seconds left.
+    pub fn active(&self) -> Option<(DustEvent, f32)> {
+        self.active
+            .as_ref()
+            .map(|(event, timer)| (*event, timer.remaining_secs()))
+    }
+
+    /// Multiplier of the dust spawner's rate from the running event.
+    pub fn spawn_multiplier(&self) -> f32 {
+        self.active
+            .as_ref()
+            .map_or(1.0, |(event, _)| event.spec().spawn_multiplier)
+    }
+}
+
+fn reset_dust_events(mut commands: Commands) {
+    commands.insert_resource(DustEvents::default());
+}
+
+fn tick_dust_event_timers(mut events: ResMut<DustEvents>, time: Res<Time>) {
+    let events = &mut *events;
+    if let Some((_, timer)) = &mut events.active {
+        timer.tick(time.delta());
+    } else {
+        // random events don't pile up behind a running one
+        events.random_timer.tick(time.delta());
+    }
+    for (_, timer) in &mut events.schedule_timers {
+        timer.tick(time.delta());
+    }
+}
+
+fn run_dust_events(
+    mut commands: Commands,
+    mut events: ResMut<DustEvents>,
+    mut rng: GlobalEntropy<WyRand>,
+) {
+    let events = &mut *events;
+    if events
+        .active
+        .as_ref()
+        .is_some_and(|(_, timer)| timer.finished())
+    {
+        events.active = None;
+    }
+
+    for (event, timer) in &mut events.schedule_timers {
+        if timer.just_finished() {
+            events.queued.push(*event);
+            if let Some((_, every)) = event.spec().schedule {
+                *timer = Timer::from_seconds(every, TimerMode::Once);
+            }
+        }
+    }
+    if events.random_timer.just_finished() {
+        let next = rng.random_range(RANDOM_EVENT_INTERVAL);
+        events.random_timer = Timer::from_seconds(next, TimerMode::Once);
+        if let Some(event) = pick_random_event(&mut rng) {
+            events.queued.push(event);
+        }
+    }
+
+    if events.active.is_none() && !events.queued.is_empty() {
+        let event = events.queued.remove(0);
+        let spec = event.spec();
+        info!("Dust event: {}", spec.banner);
+        events.active = Some((event, Timer::from_seconds(spec.duration, TimerMode::Once)));
+        commands.trigger(DustEventStarted(event));
+    }
+}
+
+fn pick_random_event(rng: &mut GlobalEntropy<WyRand>) -> Option<DustEvent> {
+    let total_weight: f32 = DustEvent::ALL
+        .iter()
+        .map(|event| event.spec().random_weight)
+        .sum();
+    if total_weight <= 0.0 {
+        return None;
+    }
+    let mut roll = rng.random_range(0.0..total_weight);
+    DustEvent::ALL.into_iter().find(|event| {
+        roll -= event.spec().random_weight;
+        roll < 0.0
+    })
+}
+
+fn spawn_boss(
+    t: Trigger<DustEventStarted>,
+    mut commands: Commands,
+    mut rng: GlobalEntropy<WyRand>,
+    area: Res<GameArea>,
+) {
+    let Some(boss) = t.0.spec().boss else {
+        return;
+    };
+    // keep the boss away from the edges
+    let margin = area.width() / 4.0;
+    let x = rng.random_range(area.min.x + margin..area.max.x - margin);
+    let speed = rng.random_range(boss.spec().speed);
+    commands.spawn(dust(Vec2::new(x, area.max.y), speed, boss, Movement::Fall));
+}
+
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+struct DustEventBanner;
+
+pub fn dust_event_banner() -> impl Bundle {
+    (
+        Name::new("Dust Event Banner"),
+        DustEventBanner,
+        Node {
+            width: Val::Percent(100.0),
+            justify_content: JustifyContent::Center,
+            padding: UiRect::all(Val::Px(5.0)),
+            display: Display::None,
+            ..default()
+        },
+        BorderRadius::all(Val::Px(5.0)),
+        BackgroundColor(WHITE.into()),
+        children![(
+            Text::new(""),
+            TextFont::from_font_size(24.0),
+            TextColor(BLACK.into()),
+        )],
+    )
+}
+
+fn update_dust_event_banner(
+    events: Res<DustEvents>,
+    banner: Single<(&mut Node, &Children), With<DustEventBanner>>,
+    mut texts: Query<&mut Text>,
+) {
+    let (mut node, children) = banner.into_inner();
+    let Some((event, secs_left)) = events.active() else {
+        node.display = Display::None;
+        return;
+    };
+    node.display = Display::Flex;
+    for child in children {
+        if let Ok(mut text) = texts.get_mut(*child) {
+            text.0 = format!("{} {:.0}s", event.spec().banner, secs_left.ceil());
+        }
+    }
+}
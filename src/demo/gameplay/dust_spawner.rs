@@ -1,15 +1,21 @@
-use bevy::math::ops::exp;
+use std::f32::consts::TAU;
 
-use super::dust::dust;
+use super::{
+    dust::{Movement, dust},
+    dust_events::DustEvents,
+};
 use crate::{
-    demo::{GAME_AREA, gameplay::dust::Dust},
+    demo::{GameArea, can_draw_gizmos, gameplay::dust::Dust},
     prelude::*,
 };
 
 pub(super) fn plugin(app: &mut App) {
     app.add_systems(Update, (spawn_dust,).in_set(AppSystems::Update));
 
-    app.add_systems(Update, gizmos.run_if(in_state(Screen::Gameplay)));
+    app.add_systems(
+        Update,
+        gizmos.run_if(in_state(Screen::Gameplay).and(can_draw_gizmos)),
+    );
 
     app.add_observer(
         |t: Trigger<SetDustSpawnStats>, mut dust_spawner: Single<&mut DustSpawner>| {
@@ -19,12 +25,12 @@ pub(super) fn plugin(app: &mut App) {
     );
 }
 
-pub fn dust_spawner() -> impl Bundle {
+pub fn dust_spawner(spawn_speed: f32, entropy: Entropy<WyRand>) -> impl Bundle {
     (
         Name::new("Dust Spawner"),
         StateScoped(Screen::Gameplay),
-        DustSpawner::new(2.0),
-        Entropy::<WyRand>::default(),
+        DustSpawner::new(spawn_speed),
+        entropy,
     )
 }
 
@@ -35,7 +41,7 @@ pub enum SetDustSpawnStats {
 
 #[derive(Component, Reflect, Debug)]
 #[reflect(Component)]
-struct DustSpawner {
+pub struct DustSpawner {
     speed: f32,
 }
 
@@ -43,53 +49,82 @@ impl DustSpawner {
     pub fn new(spawn_speed: f32) -> Self {
         Self { speed: spawn_speed }
     }
+    pub fn speed(&self) -> f32 {
+        self.speed
+    }
     fn set_speed(&mut self, speed: f32) {
         self.speed = speed;
     }
 }
 
-const A: f32 = 1.0;
-const B: f32 = 5.0;
 fn spawn_dust(
     mut commands: Commands,
     spawners: Single<(&mut DustSpawner, &mut Entropy<WyRand>)>,
+    events: Res<DustEvents>,
+    area: Res<GameArea>,
     time: Res<Time>,
-    mut big_remainder: Local<f32>,
-    mut small_remainder: Local<f32>,
+    mut remainder: Local<f32>,
 ) {
     let (spawner, mut entropy) = spawners.into_inner();
-    let expected = spawner.speed * time.delta_secs();
-    let alpha = 1.0 / (1.0 + exp(-A * (spawner.speed - B)));
-    let big_dust_acount = expected * alpha;
-    let small_dust_acount = expected * (1.0 - alpha);
-    let big_dust_count = big_dust_acount + *big_remainder;
-    let small_dust_count = small_dust_acount + *small_remainder;
-    *small_remainder = small_dust_count.fract();
-    *big_remainder = big_dust_count.fract();
+    let rate = spawner.speed * events.spawn_multiplier();
+    let count = rate * time.delta_secs() + *remainder;
+    *remainder = count.fract();
 
-    // gen small dust
-    for _ in 0..small_dust_count as usize {
-        let pos = Vec2::new(
-            entropy.random_range(GAME_AREA.min.x..GAME_AREA.max.x),
-            GAME_AREA.max.y,
-        );
-        commands.spawn(dust(pos, entropy.random_range(80.0..120.0), Dust::Small));
+    let weights = Dust::ALL.map(|species| (species.spec().spawn_weight)(spawner.speed).max(0.0));
+    let total_weight: f32 = weights.iter().sum();
+    if total_weight <= 0.0 {
+        return;
     }
+    for _ in 0..count as usize {
+        // pick a species by its spawn weight
+        let mut roll = entropy.random_range(0.0..total_weight);
+        let species = Dust::ALL
+            .into_iter()
+            .zip(weights)
+            .find(|(_, weight)| {
+                roll -= weight;
+                roll < 0.0
+            })
+            .map_or(Dust::Small, |(species, _)| species);
+
+        let pos = Vec2::new(entropy.random_range(area.min.x..area.max.x), area.max.y);
+        let movement = pick_movement(spawner.speed, &mut entropy);
+        let speed = entropy.random_range(species.spec().speed);
+        commands.spawn(dust(pos, speed, species, movement));
+    }
+}
 
-    for _ in 0..big_dust_count as usize {
-        let pos = Vec2::new(
-            entropy.random_range(GAME_AREA.min.x..GAME_AREA.max.x),
-            GAME_AREA.max.y,
-        );
+/// Pollution rate at which wobbling and swirling dust start to appear.
+const WOBBLE_FROM: f32 = 3.0;
+const SWIRL_FROM: f32 = 5.0;
 
-        commands.spawn(dust(pos, entropy.random_range(80.0..120.0), Dust::Big));
+/// Higher pollution rates bring more dust that doesn't fall straight.
+fn pick_movement(pollution: f32, entropy: &mut Entropy<WyRand>) -> Movement {
+    let wobble_chance = ((pollution - WOBBLE_FROM) / 10.0).clamp(0.0, 0.5);
+    let swirl_chance = ((pollution - SWIRL_FROM) / 10.0).clamp(0.0, 0.3);
+    let roll = entropy.random::<f32>();
+    let phase = entropy.random_range(0.0..TAU);
+    if roll < swirl_chance {
+        Movement::Swirl {
+            radius: entropy.random_range(15.0..35.0),
+            angular_speed: entropy.random_range(2.0..4.0),
+            phase,
+        }
+    } else if roll < swirl_chance + wobble_chance {
+        Movement::Wobble {
+            amplitude: entropy.random_range(20.0..50.0),
+            frequency: entropy.random_range(0.3..0.8),
+            phase,
+        }
+    } else {
+        Movement::Fall
     }
 }
 
-fn gizmos(mut gizmos: Gizmos) {
+fn gizmos(mut gizmos: Gizmos, area: Res<GameArea>) {
     gizmos.rect_2d(
-        Isometry2d::from_translation(GAME_AREA.center()),
-        GAME_AREA.size(),
+        Isometry2d::from_translation(area.center()),
+        area.size(),
         Color::BLACK,
     );
 }
@@ -3,21 +3,37 @@ use crate::prelude::*;
 mod attacker;
 mod damage;
 mod dust;
+mod dust_events;
 mod dust_spawner;
 mod health;
+mod pollution;
 mod power;
+mod spatial;
+mod species;
+mod targeting;
+mod wind;
 
-pub use attacker::SpawnAttacker;
-pub use dust_spawner::{SetDustSpawnStats, dust_spawner};
-pub use power::{SetPowerStats, power_ui};
+pub use attacker::{Attacker, AttackerStat, AttackerStats, BASE_ATTACK_ENERGY, SpawnAttacker};
+pub use damage::{AttackDustEvent, DamageType};
+pub use dust::{Dust, DustDestroyed, DustEscaped};
+pub use dust_events::dust_event_banner;
+pub use dust_spawner::{DustSpawner, SetDustSpawnStats, dust_spawner};
+pub use pollution::{PollutionMeter, pollution_ui};
+pub use power::{EnergySpent, Power, SetPowerStats, power_ui};
+pub use species::DustAverages;
+pub use targeting::{TargetingStrategy, UnlockTargetingStrategy, UnlockedStrategies};
 
 pub(super) fn plugin(app: &mut App) {
     app.add_plugins((
         attacker::plugin,
         dust::plugin,
+        dust_events::plugin,
         dust_spawner::plugin,
         damage::plugin,
         health::plugin,
+        pollution::plugin,
         power::plugin,
+        targeting::plugin,
+        wind::plugin,
     ));
 }
@@ -0,0 +1,156 @@
+//! Pollution leaked by dust escaping at the bottom of the game area.
+//!
+//! Escaped dust fills the [`PollutionMeter`], the city's air scrubbers slowly
+//! empty it again. A fuller meter cuts the data center's passive income, and
+//! in most levels the run is lost once it overflows.
+
+use bevy::color::palettes::{
+    css::{BLACK, WHITE},
+    tailwind::*,
+};
+
+use crate::{demo::level::CurrentLevel, prelude::*};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<PollutionMeter>();
+
+    app.add_systems(
+        Update,
+        update_pollution_ui.run_if(in_state(Screen::Gameplay)),
+    );
+
+    app.add_systems(
+        Update,
+        scrub_pollution
+            .in_set(AppSystems::Update)
+            .run_if(in_state(Screen::Gameplay)),
+    );
+}
+
+/// Share of the meter scrubbed per second, so a steady leak of `x` dust per
+/// second settles at `x / SCRUB_RATE`.
+const SCRUB_RATE: f32 = 0.05;
+/// Share of the passive income that is lost when the meter is full.
+const MAX_INCOME_PENALTY: f32 = 0.5;
+
+#[derive(Resource, Reflect, Debug)]
+#[reflect(Resource)]
+pub struct PollutionMeter {
+    level: f32,
+    capacity: f32,
+    /// Everything leaked this run, scrubbing doesn't lower it.
+    total_leaked: f32,
+}
+
+impl Default for PollutionMeter {
+    fn default() -> Self {
+        Self {
+            level: 0.0,
+            capacity: 100.0,
+            total_leaked: 0.0,
+        }
+    }
+}
+
+impl PollutionMeter {
+    pub fn level(&self) -> f32 {
+        self.level
+    }
+
+    /// Set the level, e.g. when restoring a save.
+    pub fn set_level(&mut self, level: f32) {
+        self.level = level.clamp(0.0, self.capacity);
+    }
+
+    pub fn fraction(&self) -> f32 {
+        self.level / self.capacity
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.level >= self.capacity
+    }
+
+    /// Add leaked pollution.
+    pub fn leak(&mut self, amount: f32) {
+        self.set_level(self.level + amount);
+        self.total_leaked += amount;
+    }
+
+    pub fn total_leaked(&self) -> f32 {
+        self.total_leaked
+    }
+
+    /// Set the total, e.g. when restoring a save.
+    pub fn set_total_leaked(&mut self, total_leaked: f32) {
+        self.total_leaked = total_leaked;
+    }
+
+    /// Multiplier of the data center's passive income.
+    pub fn income_factor(&self) -> f32 {
+        1.0 - self.fraction() * MAX_INCOME_PENALTY
+    }
+}
+
+fn scrub_pollution(
+    mut meter: ResMut<PollutionMeter>,
+    time: Res<Time>,
+    level: Option<Res<CurrentLevel>>,
+) {
+    // an overflow that loses the level stays full, the run is over
+    if level.is_some_and(|level| level.is_lost(&meter)) {
+        return;
+    }
+    let level = meter.level;
+    meter.set_level(level - level * SCRUB_RATE * time.delta_secs());
+}
+
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+struct PollutionUI;
+
+pub fn pollution_ui() -> impl Bundle {
+    (
+        Name::new("Pollution UI"),
+        Node {
+            width: Val::Percent(100.0),
+            align_items: AlignItems::Start,
+            justify_content: JustifyContent::Start,
+            padding: UiRect::all(Val::Px(3.0)),
+            ..default()
+        },
+        BackgroundColor(WHITE.into()),
+        children![(
+            Node {
+                align_items: AlignItems::Start,
+                justify_content: JustifyContent::Start,
+                width: Val::Percent(0.0),
+                overflow: Overflow::visible(),
+                ..default()
+            },
+            BackgroundColor(STONE_500.into()),
+            children![(
+                PollutionUI,
+                Text::new("Pollution: 0%"),
+                TextLayout::new(JustifyText::Left, LineBreak::NoWrap),
+                TextFont::default(),
+                TextColor(BLACK.into()),
+            )]
+        )],
+    )
+}
+
+fn update_pollution_ui(
+    mut bar: Query<&mut Node>,
+    text: Single<(&mut Text, &ChildOf), With<PollutionUI>>,
+    meter: Res<PollutionMeter>,
+) {
+    let (mut text, parent) = text.into_inner();
+    let mut bar = bar.get_mut(parent.0).expect("Pollution UI bar not found");
+
+    text.0 = format!(
+        "Pollution: {:.0}%, income x{:.2}",
+        meter.fraction() * 100.0,
+        meter.income_factor()
+    );
+    bar.width = Val::Percent(meter.fraction() * 100.0);
+}
@@ -6,7 +6,8 @@ use bevy::color::palettes::{
 use crate::prelude::*;
 
 pub(super) fn plugin(app: &mut App) {
-    app.insert_resource(Power::new(8.0, 10.0));
+    app.init_resource::<Power>();
+    app.add_event::<EnergySpent>();
 
     app.add_systems(Update, update_power_ui.run_if(in_state(Screen::Gameplay)));
 
@@ -15,10 +16,15 @@ pub(super) fn plugin(app: &mut App) {
     app.add_observer(update_power_stats);
 }
 
+/// Power spent charging discharge points in one frame.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct EnergySpent(pub f32);
+
 #[derive(Event, Debug, Clone)]
 pub enum SetPowerStats {
     RegenSpeed(f32), // Set the amount of power regenerated per second
     PowerMax(f32),   // Set the maximum power
+    Current(f32),    // Set the stored power, e.g. when restoring a save
 }
 
 fn update_power_stats(event: Trigger<SetPowerStats>, mut power: ResMut<Power>) {
@@ -29,10 +35,13 @@ fn update_power_stats(event: Trigger<SetPowerStats>, mut power: ResMut<Power>) {
         SetPowerStats::PowerMax(max) => {
             power.max = *max;
         }
+        SetPowerStats::Current(current) => {
+            power.current = current.min(power.max);
+        }
     }
 }
 
-#[derive(Resource, Reflect, Debug, Default)]
+#[derive(Resource, Reflect, Debug)]
 #[reflect(Resource)]
 pub struct Power {
     current: f32,
@@ -44,6 +53,12 @@ pub struct Power {
 #[reflect(Component)]
 struct PowerUI;
 
+impl Default for Power {
+    fn default() -> Self {
+        Self::new(8.0, 10.0)
+    }
+}
+
 impl Power {
     fn new(max: f32, regen_speed: f32) -> Self {
         Self {
@@ -57,6 +72,14 @@ impl Power {
         self.current
     }
 
+    pub fn max(&self) -> f32 {
+        self.max
+    }
+
+    pub fn regen_speed(&self) -> f32 {
+        self.regen_speed
+    }
+
     pub fn consume(&mut self, amount: f32) -> f32 {
         let output = self.current.min(amount);
         self.current = (self.current - output).max(0.0);
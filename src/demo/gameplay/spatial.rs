@@ -0,0 +1,73 @@
+//! A uniform grid over the game area for fast "what dust is near here" queries.
+//!
+//! This module only depends on Bevy types so the benchmark in
+//! `examples/dust_grid_bench.rs` can include it directly.
+
+use bevy::prelude::*;
+
+/// Side length of a grid cell. Half of the lightning range, so a radius query
+/// usually touches a handful of cells.
+pub const CELL_SIZE: f32 = 50.0;
+
+/// Buckets dust positions into square cells covering `area`.
+///
+/// Positions outside `area` are clamped into the border cells, so dust that
+/// slightly leaves the area is still found by queries.
+#[derive(Resource, Debug, Clone)]
+pub struct DustGrid {
+    area: Rect,
+    cell_size: f32,
+    cols: usize,
+    rows: usize,
+    cells: Vec<Vec<(Entity, Vec2)>>,
+}
+
+impl DustGrid {
+    pub fn new(area: Rect, cell_size: f32) -> Self {
+        let size = area.size();
+        let cols = ((size.x / cell_size).ceil() as usize).max(1);
+        let rows = ((size.y / cell_size).ceil() as usize).max(1);
+        Self {
+            area,
+            cell_size,
+            cols,
+            rows,
+            cells: vec![Vec::new(); cols * rows],
+        }
+    }
+
+    /// Remove every entry but keep the allocated cells.
+    pub fn clear(&mut self) {
+        for cell in &mut self.cells {
+            cell.clear();
+        }
+    }
+
+    pub fn insert(&mut self, entity: Entity, pos: Vec2) {
+        let (col, row) = self.cell_of(pos);
+        self.cells[row * self.cols + col].push((entity, pos));
+    }
+
+    /// Every entry within `radius` of `pos`.
+    pub fn within(&self, pos: Vec2, radius: f32) -> impl Iterator<Item = (Entity, Vec2)> + '_ {
+        let radius_squared = radius * radius;
+        let (min_col, min_row) = self.cell_of(pos - Vec2::splat(radius));
+        let (max_col, max_row) = self.cell_of(pos + Vec2::splat(radius));
+        (min_row..=max_row)
+            .flat_map(move |row| (min_col..=max_col).map(move |col| row * self.cols + col))
+            .flat_map(move |index| self.cells[index].iter().copied())
+            .filter(move |(_, other)| other.distance_squared(pos) < radius_squared)
+    }
+
+    /// Whether any entry lies within `radius` of `pos`.
+    pub fn any_within(&self, pos: Vec2, radius: f32) -> bool {
+        self.within(pos, radius).next().is_some()
+    }
+
+    fn cell_of(&self, pos: Vec2) -> (usize, usize) {
+        let local = (pos - self.area.min) / self.cell_size;
+        let col = (local.x.max(0.0) as usize).min(self.cols - 1);
+        let row = (local.y.max(0.0) as usize).min(self.rows - 1);
+        (col, row)
+    }
+}
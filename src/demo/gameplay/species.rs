@@ -0,0 +1,233 @@
+//! What makes each kind of [`Dust`] different, declared in one table.
+
+use std::ops::Range;
+
+use bevy::{
+    color::palettes::css::{CHARTREUSE, DARK_SLATE_GRAY, GOLD, HOT_PINK, SLATE_GRAY, YELLOW},
+    math::ops::exp,
+};
+
+use crate::prelude::*;
+
+use super::{
+    damage::DamageType,
+    dust::{DUST_HEALTH, Dust},
+};
+
+pub(super) struct DustSpec {
+    pub health: f32,
+    /// Dust data earned when it is destroyed.
+    pub reward: u32,
+    pub color: Color,
+    pub size: f32,
+    /// Falling speed is picked from this range.
+    pub speed: Range<f32>,
+    /// How strongly the wind pushes it.
+    pub drift: f32,
+    /// Damage of these types is multiplied by the factor.
+    pub resists: &'static [(DamageType, f32)],
+    /// Health healed per second.
+    pub regen: f32,
+    /// Pollution leaked into the city when it escapes at the bottom.
+    pub leak: f32,
+    /// Power drained when it escapes at the bottom.
+    pub escape_drain: f32,
+    pub on_death: OnDeath,
+    /// Relative chance to spawn at a pollution rate.
+    pub spawn_weight: fn(f32) -> f32,
+}
+
+pub(super) enum OnDeath {
+    Nothing,
+    /// Break into smaller dust scattered around.
+    Split {
+        into: Dust,
+        count: usize,
+        spread: f32,
+    },
+}
+
+/// Share of big dust among small and big, rising with the pollution rate.
+fn big_share(pollution: f32) -> f32 {
+    1.0 / (1.0 + exp(-(pollution - 5.0)))
+}
+
+/// `weight` once the pollution rate reaches `from`.
+fn from_pollution(pollution: f32, from: f32, weight: f32) -> f32 {
+    if pollution >= from { weight } else { 0.0 }
+}
+
+impl Dust {
+    pub const ALL: [Self; 8] = [
+        Self::Small,
+        Self::Big,
+        Self::Armored,
+        Self::Toxic,
+        Self::Regenerating,
+        Self::Golden,
+        Self::Spark,
+        Self::Mega,
+    ];
+
+    pub(super) fn spec(&self) -> DustSpec {
+        let base = DustSpec {
+            health: DUST_HEALTH,
+            reward: 1,
+            color: Color::WHITE,
+            size: 16.0,
+            speed: 80.0..120.0,
+            drift: 1.0,
+            resists: &[],
+            regen: 0.0,
+            leak: 1.0,
+            escape_drain: 0.0,
+            on_death: OnDeath::Nothing,
+            spawn_weight: |pollution| 1.0 - big_share(pollution),
+        };
+        match self {
+            Self::Small => base,
+            Self::Big => DustSpec {
+                health: DUST_HEALTH * 2.0,
+                reward: 2,
+                color: Color::BLACK,
+                size: 20.0,
+                drift: 0.5,
+                leak: 2.0,
+                on_death: OnDeath::Split {
+                    into: Self::Small,
+                    count: 4,
+                    spread: 100.0,
+                },
+                spawn_weight: big_share,
+                ..base
+            },
+            Self::Armored => DustSpec {
+                health: DUST_HEALTH * 3.0,
+                reward: 4,
+                color: SLATE_GRAY.into(),
+                size: 18.0,
+                speed: 60.0..90.0,
+                drift: 0.3,
+                resists: &[(DamageType::Lightning, 0.25)],
+                leak: 3.0,
+                spawn_weight: |pollution| from_pollution(pollution, 4.0, 0.1),
+                ..base
+            },
+            Self::Toxic => DustSpec {
+                health: DUST_HEALTH * 1.2,
+                reward: 2,
+                color: CHARTREUSE.into(),
+                leak: 4.0,
+                escape_drain: 3.0,
+                spawn_weight: |pollution| from_pollution(pollution, 6.0, 0.08),
+                ..base
+            },
+            Self::Regenerating => DustSpec {
+                health: DUST_HEALTH * 1.6,
+                reward: 3,
+                color: HOT_PINK.into(),
+                regen: 2.0,
+                leak: 1.5,
+                spawn_weight: |pollution| from_pollution(pollution, 5.0, 0.1),
+                ..base
+            },
+            Self::Golden => DustSpec {
+                reward: 15,
+                color: GOLD.into(),
+                size: 14.0,
+                speed: 100.0..140.0,
+                leak: 0.5,
+                spawn_weight: |_| 0.02,
+                ..base
+            },
+            Self::Spark => DustSpec {
+                health: DUST_HEALTH * 0.4,
+                reward: 2,
+                color: YELLOW.into(),
+                size: 10.0,
+                speed: 200.0..260.0,
+                drift: 1.5,
+                leak: 0.5,
+                spawn_weight: |pollution| from_pollution(pollution, 3.0, 0.15),
+                ..base
+            },
+            Self::Mega => DustSpec {
+                health: DUST_HEALTH * 40.0,
+                reward: 50,
+                color: DARK_SLATE_GRAY.into(),
+                size: 40.0,
+                speed: 25.0..35.0,
+                drift: 0.1,
+                leak: 20.0,
+                on_death: OnDeath::Split {
+                    into: Self::Big,
+                    count: 6,
+                    spread: 150.0,
+                },
+                spawn_weight: |_| 0.0,
+                ..base
+            },
+        }
+    }
+}
+
+impl DustSpec {
+    /// Factor applied to damage of `damage_type`.
+    pub fn damage_taken(&self, damage_type: DamageType) -> f32 {
+        self.resists
+            .iter()
+            .find(|(resisted, _)| *resisted == damage_type)
+            .map_or(1.0, |(_, factor)| *factor)
+    }
+}
+
+/// What a spawned dust is worth on average, counting the pieces it splits into.
+#[derive(Debug, Clone, Copy)]
+pub struct DustAverages {
+    /// Damage it takes to clear it.
+    pub health: f32,
+    /// Dust data earned once it is cleared.
+    pub reward: f32,
+}
+
+impl DustAverages {
+    /// Averages over the species spawning at a pollution rate, `None` if none
+    /// spawn.
+    pub fn at(pollution: f32) -> Option<Self> {
+        let mut total = Self {
+            health: 0.0,
+            reward: 0.0,
+        };
+        let mut total_weight = 0.0;
+        for species in Dust::ALL {
+            let weight = (species.spec().spawn_weight)(pollution).max(0.0);
+            let cleared = Self::cleared(species);
+            total.health += weight * cleared.health;
+            total.reward += weight * cleared.reward;
+            total_weight += weight;
+        }
+        (total_weight > 0.0).then(|| Self {
+            health: total.health / total_weight,
+            reward: total.reward / total_weight,
+        })
+    }
+
+    /// Health and reward of one dust and everything it splits into.
+    fn cleared(species: Dust) -> Self {
+        let spec = species.spec();
+        let own = Self {
+            health: spec.health,
+            reward: spec.reward as f32,
+        };
+        match spec.on_death {
+            OnDeath::Nothing => own,
+            OnDeath::Split { into, count, .. } => {
+                let piece = Self::cleared(into);
+                Self {
+                    health: own.health + piece.health * count as f32,
+                    reward: own.reward + piece.reward * count as f32,
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,169 @@
+//! Which dust an attack goes for.
+//!
+//! Every attacker has a [`TargetingStrategy`], consulted by `deal_damage` for
+//! each lightning hop and for the aim of fire and laser. Right-click an
+//! attacker to cycle through the strategies unlocked in the Research Lab.
+
+use std::collections::BTreeSet;
+
+use bevy::color::palettes::css::WHITE;
+use serde::{Deserialize, Serialize};
+
+use crate::prelude::*;
+
+use super::{attacker::Attacker, dust::Dust, health::Health};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<UnlockedStrategies>();
+    app.add_observer(
+        |t: Trigger<UnlockTargetingStrategy>, mut unlocked: ResMut<UnlockedStrategies>| {
+            unlocked.0.insert(t.0);
+        },
+    );
+    app.add_systems(
+        Update,
+        update_strategy_label
+            .run_if(any_match_filter::<Changed<TargetingStrategy>>)
+            .in_set(AppSystems::Update),
+    );
+}
+
+#[derive(
+    Component,
+    Reflect,
+    Serialize,
+    Deserialize,
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+)]
+#[reflect(Component)]
+pub enum TargetingStrategy {
+    #[default]
+    Nearest,
+    LowestHealth,
+    HighestHealth,
+    /// Big dust first, the nearest one among equals.
+    BigFirst,
+    /// The dust closest to escaping at the bottom of the screen.
+    LowestOnScreen,
+}
+
+impl TargetingStrategy {
+    fn label(self) -> &'static str {
+        match self {
+            Self::Nearest => "Nearest",
+            Self::LowestHealth => "Weakest",
+            Self::HighestHealth => "Strongest",
+            Self::BigFirst => "Big",
+            Self::LowestOnScreen => "Lowest",
+        }
+    }
+
+    /// Pick a target at `pos` from `candidates`, dust that can't be found in
+    /// `dust` is ignored.
+    pub(super) fn pick(
+        self,
+        pos: Vec2,
+        candidates: impl Iterator<Item = (Entity, Vec2)>,
+        dust: &Query<(&mut Health, &Transform, &Dust)>,
+    ) -> Option<(Entity, Vec2)> {
+        let key = |(entity, target): &(Entity, Vec2)| -> (f32, f32) {
+            let distance = target.distance_squared(pos);
+            let Ok((health, _, kind)) = dust.get(*entity) else {
+                return (f32::INFINITY, distance);
+            };
+            let primary = match self {
+                Self::Nearest => 0.0,
+                Self::LowestHealth => health.current(),
+                Self::HighestHealth => -health.current(),
+                Self::BigFirst => match kind {
+                    Dust::Mega | Dust::Big => 0.0,
+                    _ => 1.0,
+                },
+                Self::LowestOnScreen => target.y,
+            };
+            (primary, distance)
+        };
+        candidates
+            .filter(|(entity, _)| dust.contains(*entity))
+            .min_by(|a, b| {
+                let (a, b) = (key(a), key(b));
+                a.0.total_cmp(&b.0).then(a.1.total_cmp(&b.1))
+            })
+    }
+}
+
+/// Strategies attackers can be switched to, [`TargetingStrategy::Nearest`] is
+/// always available.
+#[derive(Resource, Debug, Clone)]
+pub struct UnlockedStrategies(BTreeSet<TargetingStrategy>);
+
+impl Default for UnlockedStrategies {
+    fn default() -> Self {
+        Self(BTreeSet::from([TargetingStrategy::Nearest]))
+    }
+}
+
+impl UnlockedStrategies {
+    /// The unlocked strategy after `current`, wrapping around.
+    fn after(&self, current: TargetingStrategy) -> TargetingStrategy {
+        self.0
+            .range(current..)
+            .nth(1)
+            .or_else(|| self.0.first())
+            .copied()
+            .unwrap_or_default()
+    }
+}
+
+#[derive(Event, Debug, Clone, Copy)]
+pub struct UnlockTargetingStrategy(pub TargetingStrategy);
+
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+struct StrategyLabel;
+
+/// Label shown under an attacker.
+pub(super) fn strategy_label(strategy: TargetingStrategy) -> impl Bundle {
+    (
+        Name::new("Strategy Label"),
+        StrategyLabel,
+        Text2d::new(strategy.label()),
+        TextFont::from_font_size(10.0),
+        TextColor(WHITE.into()),
+        Transform::from_xyz(0.0, -14.0, 0.0),
+        Pickable::IGNORE,
+    )
+}
+
+/// Right-click an attacker to switch to the next unlocked strategy.
+pub(super) fn cycle_strategy(
+    t: Trigger<Pointer<Click>>,
+    mut strategies: Query<&mut TargetingStrategy, With<Attacker>>,
+    unlocked: Res<UnlockedStrategies>,
+) {
+    if t.button != PointerButton::Secondary {
+        return;
+    }
+    if let Ok(mut strategy) = strategies.get_mut(t.target()) {
+        *strategy = unlocked.after(*strategy);
+    }
+}
+
+fn update_strategy_label(
+    attackers: Query<(&TargetingStrategy, &Children), Changed<TargetingStrategy>>,
+    mut labels: Query<&mut Text2d, With<StrategyLabel>>,
+) {
+    for (strategy, children) in attackers {
+        let mut labels = labels.iter_many_mut(children);
+        while let Some(mut label) = labels.fetch_next() {
+            label.0 = strategy.label().to_string();
+        }
+    }
+}
@@ -0,0 +1,111 @@
+//! A global wind that pushes dust sideways.
+//!
+//! The wind slowly turns towards a new random heading every few seconds, and
+//! now and then a short gust blows on top of it.
+
+use bevy::math::ops::exp;
+
+use crate::{
+    demo::{GameArea, can_draw_gizmos},
+    prelude::*,
+};
+
+use super::dust_spawner::DustSpawner;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<Wind>();
+    app.add_systems(OnEnter(Screen::Gameplay), reset_wind);
+    app.add_systems(Update, tick_wind_timers.in_set(AppSystems::TickTimers));
+    app.add_systems(
+        Update,
+        change_wind
+            .in_set(AppSystems::Update)
+            .run_if(in_state(Screen::Gameplay)),
+    );
+    app.add_systems(
+        Update,
+        draw_wind.run_if(in_state(Screen::Gameplay).and(can_draw_gizmos)),
+    );
+}
+
+/// Strongest steady wind, in pixels per second, at the lowest pollution rate.
+const BASE_MAX_WIND: f32 = 20.0;
+/// Extra steady wind per particle per second of pollution rate.
+const MAX_WIND_PER_POLLUTION: f32 = 4.0;
+const GUST_STRENGTH: std::ops::Range<f32> = 40.0..80.0;
+/// How fast a gust dies down, per second.
+const GUST_DECAY: f32 = 1.5;
+/// How fast the steady wind turns towards its new heading, per second.
+const WIND_RESPONSE: f32 = 0.5;
+
+#[derive(Resource, Reflect, Debug)]
+#[reflect(Resource)]
+pub struct Wind {
+    steady: f32,
+    target: f32,
+    gust: f32,
+    change_timer: Timer,
+    gust_timer: Timer,
+}
+
+impl Default for Wind {
+    fn default() -> Self {
+        Self {
+            steady: 0.0,
+            target: 0.0,
+            gust: 0.0,
+            change_timer: Timer::from_seconds(8.0, TimerMode::Repeating),
+            gust_timer: Timer::from_seconds(15.0, TimerMode::Once),
+        }
+    }
+}
+
+impl Wind {
+    /// Horizontal wind speed in pixels per second, positive blows right.
+    pub fn speed(&self) -> f32 {
+        self.steady + self.gust
+    }
+}
+
+fn reset_wind(mut commands: Commands) {
+    commands.insert_resource(Wind::default());
+}
+
+fn tick_wind_timers(mut wind: ResMut<Wind>, time: Res<Time>) {
+    wind.change_timer.tick(time.delta());
+    wind.gust_timer.tick(time.delta());
+}
+
+fn change_wind(
+    mut wind: ResMut<Wind>,
+    spawner: Option<Single<&DustSpawner>>,
+    mut rng: GlobalEntropy<WyRand>,
+    time: Res<Time>,
+) {
+    // more pollution, stronger winds
+    let pollution = spawner.map_or(0.0, |spawner| spawner.speed());
+    let max_wind = BASE_MAX_WIND + pollution * MAX_WIND_PER_POLLUTION;
+    if wind.change_timer.just_finished() {
+        wind.target = rng.random_range(-max_wind..=max_wind);
+    }
+    if wind.gust_timer.just_finished() {
+        let direction = if rng.random_bool(0.5) { 1.0 } else { -1.0 };
+        wind.gust = direction * rng.random_range(GUST_STRENGTH);
+        let next_gust = rng.random_range(10.0..20.0);
+        wind.gust_timer = Timer::from_seconds(next_gust, TimerMode::Once);
+    }
+
+    let dt = time.delta_secs();
+    let steady = wind.steady;
+    wind.steady += (wind.target - steady) * (1.0 - exp(-WIND_RESPONSE * dt));
+    wind.gust *= exp(-GUST_DECAY * dt);
+}
+
+fn draw_wind(wind: Res<Wind>, area: Res<GameArea>, mut gizmos: Gizmos) {
+    let start = Vec2::new(area.center().x, area.max.y + 15.0);
+    gizmos.arrow_2d(
+        start,
+        start + Vec2::X * wind.speed(),
+        Color::WHITE.with_alpha(0.5),
+    );
+}
@@ -1,13 +1,27 @@
 //! Spawn the main level.
+//!
+//! Levels are defined in `assets/data/levels.ron`, see [`catalog`]. The level
+//! select menu inserts the [`CurrentLevel`] before gameplay starts.
 
-use crate::demo::ui::collect_rate::goal_ui;
+pub mod catalog;
+
+use crate::demo::ui::{
+    attacker_panel::attacker_panel, charts::economy_charts, collect_rate::collect_rate_ui,
+};
 use crate::prelude::*;
 
-use crate::{AssetsState, audio::music, screens::Screen};
+use crate::{AssetsState, LoadErrors, audio::music, screens::Screen};
 
+use super::GameArea;
+use super::currency::Currency;
 use super::gameplay::*;
-use super::ui::inventory::inventory_ui;
-use super::ui::purchase::ShopState;
+use super::objectives::objective_panel;
+use super::ui::inventory::{Inventory, inventory_ui};
+use super::ui::purchase::{
+    ShopState,
+    catalog::{UpgradeAssets, UpgradeCatalog},
+};
+use catalog::*;
 
 pub(super) fn plugin(app: &mut App) {
     app.configure_loading_state(
@@ -15,6 +29,13 @@ pub(super) fn plugin(app: &mut App) {
     );
 
     app.register_type::<LevelAssets>();
+
+    app.init_asset::<LevelCatalog>();
+    app.init_asset_loader::<LevelCatalogLoader>();
+    app.configure_loading_state(
+        LoadingStateConfig::new(AssetsState::Loading).load_collection::<LevelCatalogAssets>(),
+    );
+    app.add_systems(OnEnter(AssetsState::Done), insert_level_catalog);
 }
 
 #[derive(Resource, AssetCollection, Clone, Reflect)]
@@ -24,13 +45,78 @@ pub struct LevelAssets {
     music: Handle<AudioSource>,
 }
 
+/// The level being played, or about to be.
+#[derive(Resource, Debug, Clone, Deref)]
+pub struct CurrentLevel(pub LevelDef);
+
+/// Run condition: the current level's loss condition is met.
+pub fn run_lost(level: Option<Res<CurrentLevel>>, meter: Option<Res<PollutionMeter>>) -> bool {
+    level
+        .zip(meter)
+        .is_some_and(|(level, meter)| level.is_lost(&meter))
+}
+
+fn insert_level_catalog(
+    mut commands: Commands,
+    level_assets: Res<LevelCatalogAssets>,
+    catalogs: Res<Assets<LevelCatalog>>,
+    upgrade_assets: Res<UpgradeAssets>,
+    upgrade_catalogs: Res<Assets<UpgradeCatalog>>,
+    mut load_errors: ResMut<LoadErrors>,
+    mut next_state: ResMut<NextState<AssetsState>>,
+) {
+    let Some(upgrades) = upgrade_catalogs.get(&upgrade_assets.catalog) else {
+        return;
+    };
+    if let Some(catalog) = catalogs.get(&level_assets.catalog) {
+        // the levels can't be played, show it like any other invalid asset
+        if let Err(error) = catalog.check_upgrades(upgrades) {
+            load_errors.0.push(error.to_string());
+            next_state.set(AssetsState::Failed);
+            return;
+        }
+        info!("Loaded {} levels", catalog.levels.len());
+        // validation guarantees at least one level
+        commands.insert_resource(CurrentLevel(catalog.levels[0].clone()));
+        commands.insert_resource(catalog.clone());
+    }
+}
+
+/// Set up a level's arena, starting resources and discharge points. The dust
+/// spawner is spawned by the caller, with [`LevelDef::spawn_speed`].
+pub(super) fn start_level(level: &LevelDef, inventory: &mut Inventory, commands: &mut Commands) {
+    commands.insert_resource(GameArea(level.arena.rect()));
+
+    let start = &level.start;
+    inventory.dust_data = Currency::from(start.dust_data);
+    commands.trigger(SetPowerStats::PowerMax(start.power_max));
+    commands.trigger(SetPowerStats::RegenSpeed(start.power_regen));
+    commands.trigger(SetPowerStats::Current(start.power_max));
+
+    // line the starting discharge points up in the middle of the arena
+    let center = level.arena.rect().center();
+    let count = start.discharge_points.len();
+    for (i, damage_type) in start.discharge_points.iter().enumerate() {
+        let offset = (i as f32 - (count as f32 - 1.0) / 2.0) * 40.0;
+        commands.trigger(SpawnAttacker {
+            pos: center + Vec2::X * offset,
+            damage_type: *damage_type,
+            ..default()
+        });
+    }
+}
+
 /// A system that spawns the main level.
 pub fn spawn_level(
     mut commands: Commands,
     level_assets: Res<LevelAssets>,
+    level: Res<CurrentLevel>,
     shop_state: Res<ShopState>,
+    catalog: Res<UpgradeCatalog>,
+    mut inventory: ResMut<Inventory>,
+    mut entropy: GlobalEntropy<WyRand>,
 ) {
-    info!("Spawn level");
+    info!("Spawn level {}", level.id);
 
     commands.spawn((
         Name::new("Level"),
@@ -42,11 +128,11 @@ pub fn spawn_level(
                 Name::new("Gameplay Music"),
                 music(level_assets.music.clone())
             ),
-            dust_spawner(),
+            dust_spawner(level.spawn_speed, entropy.fork_rng()),
         ],
     ));
 
-    commands.trigger(SpawnAttacker);
+    start_level(&level, &mut inventory, &mut commands);
 
     // ingame ui
     commands.spawn((
@@ -75,9 +161,17 @@ pub fn spawn_level(
                     ..default()
                 },
                 Pickable::IGNORE,
-                children![goal_ui(), power_ui(),]
+                children![
+                    dust_event_banner(),
+                    objective_panel(&level.objectives),
+                    collect_rate_ui(),
+                    economy_charts(),
+                    power_ui(),
+                    pollution_ui(),
+                    attacker_panel()
+                ]
             ),
-            shop_state.render()
+            shop_state.render(&catalog, &level)
         ],
     ));
 }
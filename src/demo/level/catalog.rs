@@ -0,0 +1,311 @@
+//! Level definitions, loaded from `assets/data/levels.ron`.
+
+use std::collections::HashSet;
+
+use bevy::asset::{AssetLoader, LoadContext, io::Reader};
+use serde::Deserialize;
+
+use crate::{
+    demo::{
+        currency::{Currency, NumberFormat},
+        gameplay::{DamageType, PollutionMeter},
+        ui::purchase::catalog::{Prerequisite, UpgradeCatalog},
+    },
+    prelude::*,
+};
+
+#[derive(Resource, AssetCollection)]
+pub struct LevelCatalogAssets {
+    #[asset(path = "data/levels.ron")]
+    pub catalog: Handle<LevelCatalog>,
+}
+
+/// Every level shown in the level select menu, in display order.
+#[derive(Asset, Resource, TypePath, Deserialize, Debug, Clone, Default)]
+pub struct LevelCatalog {
+    pub levels: Vec<LevelDef>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct LevelDef {
+    /// Stable key stored in saves.
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub arena: Arena,
+    /// Dust spawned per second before any upgrade.
+    pub spawn_speed: f32,
+    #[serde(default)]
+    pub start: StartingResources,
+    /// Research Lab upgrade ids sold in this level, `None` for all of them.
+    #[serde(default)]
+    pub allowed_upgrades: Option<Vec<String>>,
+    /// Goals shown in the objective panel, see [`Objective`].
+    pub objectives: Vec<Objective>,
+    /// How many objectives complete the level, `None` for all of them.
+    #[serde(default)]
+    pub required_objectives: Option<usize>,
+    #[serde(default)]
+    pub lose: Option<LossCondition>,
+}
+
+/// The rectangle dust falls through and discharge points work in.
+#[derive(Deserialize, Debug, Clone, Copy)]
+pub struct Arena {
+    pub center: (f32, f32),
+    pub size: (f32, f32),
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct StartingResources {
+    pub dust_data: u32,
+    pub power_max: f32,
+    pub power_regen: f32,
+    /// One discharge point of each damage type is placed at the start.
+    pub discharge_points: Vec<DamageType>,
+}
+
+/// A goal of a level. Once met, an objective stays met for the rest of the
+/// run, see [`crate::demo::objectives`].
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub enum Objective {
+    /// Reach a smoothed collect rate, in dust data per second.
+    CollectRate(f64),
+    /// Collect this much dust data in one run.
+    Collected(Currency),
+    /// Play this many minutes while leaking less than `max_leaked` pollution
+    /// in total. Failed for the rest of the run once too much leaked.
+    Survive { minutes: f32, max_leaked: f32 },
+    /// Buy a Research Lab upgrade up to `level`.
+    UpgradeLevel { id: String, level: usize },
+}
+
+/// How a level is lost.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LossCondition {
+    /// The pollution meter fills up.
+    PollutionOverflow,
+}
+
+impl Arena {
+    pub fn rect(&self) -> Rect {
+        Rect::from_center_size(self.center.into(), self.size.into())
+    }
+}
+
+impl Default for StartingResources {
+    fn default() -> Self {
+        Self {
+            dust_data: 0,
+            power_max: 8.0,
+            power_regen: 10.0,
+            discharge_points: vec![DamageType::Lightning],
+        }
+    }
+}
+
+impl Objective {
+    /// The goal in words, naming upgrades as the `catalog` does.
+    pub fn describe(&self, catalog: &UpgradeCatalog, format: NumberFormat) -> String {
+        match self {
+            Self::CollectRate(rate) => {
+                format!("Reach {} dust/s", format.format(Currency::from_f64(*rate)))
+            }
+            Self::Collected(amount) => format!("Collect {} dust data", format.format(*amount)),
+            Self::Survive {
+                minutes,
+                max_leaked,
+            } => format!("Last {minutes} min, leaking under {max_leaked}"),
+            Self::UpgradeLevel { id, level } => {
+                let name = catalog.get(id).map_or(id.as_str(), |upgrade| &upgrade.name);
+                format!("Buy {name} to level {level}")
+            }
+        }
+    }
+}
+
+impl LossCondition {
+    pub fn is_met(&self, meter: &PollutionMeter) -> bool {
+        match self {
+            Self::PollutionOverflow => meter.is_full(),
+        }
+    }
+}
+
+impl LevelDef {
+    pub fn is_lost(&self, meter: &PollutionMeter) -> bool {
+        self.lose.is_some_and(|lose| lose.is_met(meter))
+    }
+
+    /// Number of met objectives that complete the level.
+    pub fn objectives_to_complete(&self) -> usize {
+        self.required_objectives.unwrap_or(self.objectives.len())
+    }
+
+    /// The objectives in one line, for menus.
+    pub fn describe_objectives(&self, catalog: &UpgradeCatalog, format: NumberFormat) -> String {
+        let objectives = self
+            .objectives
+            .iter()
+            .map(|objective| objective.describe(catalog, format))
+            .collect::<Vec<_>>()
+            .join(", ");
+        match self.required_objectives {
+            Some(required) if required < self.objectives.len() => {
+                format!("{required} of: {objectives}")
+            }
+            _ => objectives,
+        }
+    }
+
+    /// Whether the Research Lab sells the upgrade in this level.
+    pub fn allows(&self, upgrade_id: &str) -> bool {
+        self.allowed_upgrades
+            .as_ref()
+            .is_none_or(|allowed| allowed.iter().any(|id| id == upgrade_id))
+    }
+}
+
+impl LevelCatalog {
+    pub fn get(&self, id: &str) -> Option<&LevelDef> {
+        self.levels.iter().find(|level| level.id == id)
+    }
+
+    /// Parse and validate a RON level catalog.
+    pub fn from_ron(bytes: &[u8]) -> Result<Self, LevelCatalogError> {
+        let catalog: Self = ron::de::from_bytes(bytes)?;
+        catalog.validate()?;
+        Ok(catalog)
+    }
+
+    fn validate(&self) -> Result<(), LevelCatalogError> {
+        if self.levels.is_empty() {
+            return Err(LevelCatalogError::Empty);
+        }
+        let mut ids = HashSet::new();
+        for level in &self.levels {
+            let invalid = |reason: &str| LevelCatalogError::Invalid {
+                id: level.id.clone(),
+                reason: reason.to_string(),
+            };
+            if level.id.is_empty() {
+                return Err(invalid("id is empty"));
+            }
+            if !ids.insert(level.id.as_str()) {
+                return Err(invalid("id is used more than once"));
+            }
+            let (width, height) = level.arena.size;
+            if !(width.is_finite() && width > 0.0 && height.is_finite() && height > 0.0) {
+                return Err(invalid("arena size must be positive"));
+            }
+            if !(level.spawn_speed.is_finite() && level.spawn_speed >= 0.0) {
+                return Err(invalid("spawn speed must be a non-negative number"));
+            }
+            if level.start.power_max <= 0.0 {
+                return Err(invalid("starting power must be positive"));
+            }
+            if level.objectives.is_empty() {
+                return Err(invalid("there are no objectives"));
+            }
+            if !(1..=level.objectives.len()).contains(&level.objectives_to_complete()) {
+                return Err(invalid(
+                    "required objectives must be between 1 and the number of objectives",
+                ));
+            }
+            let bad_survive = level.objectives.iter().any(|objective| {
+                matches!(objective, Objective::Survive { minutes, .. }
+                    if !(minutes.is_finite() && *minutes > 0.0))
+            });
+            if bad_survive {
+                return Err(invalid("survive minutes must be positive"));
+            }
+        }
+        Ok(())
+    }
+
+    /// Check the upgrades the levels name against the upgrade `catalog`, so
+    /// every objective can be met. The catalogs load separately, so this
+    /// can't be part of [`Self::from_ron`].
+    pub fn check_upgrades(&self, catalog: &UpgradeCatalog) -> Result<(), LevelCatalogError> {
+        for level in &self.levels {
+            let invalid = |reason: String| LevelCatalogError::Invalid {
+                id: level.id.clone(),
+                reason,
+            };
+            for id in level.allowed_upgrades.iter().flatten() {
+                let Some(upgrade) = catalog.get(id) else {
+                    return Err(invalid(format!("unknown upgrade `{id}`")));
+                };
+                // a row whose prerequisite isn't sold could never be bought
+                let unsold = upgrade
+                    .requires
+                    .iter()
+                    .find_map(|prerequisite| match prerequisite {
+                        Prerequisite::Upgrade { id, .. } if !level.allows(id) => Some(id),
+                        _ => None,
+                    });
+                if let Some(required) = unsold {
+                    return Err(invalid(format!(
+                        "`{id}` requires `{required}`, which is not sold in this level"
+                    )));
+                }
+            }
+            for objective in &level.objectives {
+                let Objective::UpgradeLevel { id, level: wanted } = objective else {
+                    continue;
+                };
+                let Some(upgrade) = catalog.get(id) else {
+                    return Err(invalid(format!("unknown upgrade `{id}`")));
+                };
+                if !level.allows(id) {
+                    return Err(invalid(format!(
+                        "objective upgrade `{id}` is not sold in this level"
+                    )));
+                }
+                if upgrade.max_level().is_some_and(|max| *wanted > max) {
+                    return Err(invalid(format!(
+                        "objective level of `{id}` is above its max level"
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum LevelCatalogError {
+    #[error("could not read level catalog: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("could not parse level catalog: {0}")]
+    Ron(#[from] ron::error::SpannedError),
+    #[error("level catalog has no levels")]
+    Empty,
+    #[error("invalid level `{id}`: {reason}")]
+    Invalid { id: String, reason: String },
+}
+
+#[derive(Default)]
+pub struct LevelCatalogLoader;
+
+impl AssetLoader for LevelCatalogLoader {
+    type Asset = LevelCatalog;
+    type Settings = ();
+    type Error = LevelCatalogError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        LevelCatalog::from_ron(&bytes)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["levels.ron"]
+    }
+}
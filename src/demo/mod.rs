@@ -1,40 +1,96 @@
 use crate::prelude::*;
 
+pub mod achievements;
+pub mod currency;
 mod gameplay;
 pub mod level;
+pub mod objectives;
+pub mod offline;
+pub mod prestige;
+pub mod save;
+#[cfg(feature = "headless")]
+pub mod simulation;
+pub mod statistics;
 mod ui;
 
-#[cfg(feature = "dev")]
-pub use ui::inventory::Inventory;
+pub use ui::{inventory::Inventory, purchase::catalog::UpgradeCatalog};
 
-const GAME_AREA: Rect = Rect {
-    min: Vec2::new(-200.0, -350.0),
-    max: Vec2::new(200.0, 250.0),
-};
+/// The current level's arena, see [`level::catalog::Arena`]. Dust falls
+/// through it and discharge points only work inside it.
+#[derive(Resource, Reflect, Debug, Clone, Copy, PartialEq, Deref)]
+#[reflect(Resource)]
+pub struct GameArea(pub Rect);
+
+impl Default for GameArea {
+    fn default() -> Self {
+        Self(Rect {
+            min: Vec2::new(-200.0, -350.0),
+            max: Vec2::new(200.0, 250.0),
+        })
+    }
+}
 
 pub(super) fn plugin(app: &mut App) {
-    app.add_plugins((level::plugin, ui::plugin, gameplay::plugin));
+    app.add_plugins((
+        currency::plugin,
+        level::plugin,
+        save::plugin,
+        offline::plugin,
+        prestige::plugin,
+        achievements::plugin,
+        statistics::plugin,
+        rules_plugin,
+    ));
+}
+
+/// Run condition: gizmos can be drawn. They only exist when rendering, the
+/// headless simulation runs without them.
+pub fn can_draw_gizmos(store: Option<Res<GizmoConfigStore>>) -> bool {
+    store.is_some()
+}
+
+/// The gameplay and economy, without the level assets.
+/// The headless simulation runs this on its own.
+fn rules_plugin(app: &mut App) {
+    app.add_plugins((ui::plugin, gameplay::plugin, objectives::plugin));
 
-    app.insert_resource(PlayerStats {
-        attack_energy: 5.0, // Initial attack energy
-    });
+    // the stored format when running the whole game, the default headless
+    app.init_resource::<currency::NumberFormat>();
+    app.init_resource::<GameArea>();
+    app.init_resource::<PlayerStats>();
 
     app.add_observer(
         |t: Trigger<ChangePlayerStats>, mut player_stats: ResMut<PlayerStats>| match t.event() {
-            ChangePlayerStats::SetAttackEnergy(amount) => {
-                player_stats.attack_energy = *amount;
+            ChangePlayerStats::SetEnergyMultiplier(multiplier) => {
+                player_stats.energy_multiplier = *multiplier;
+            }
+            ChangePlayerStats::SetRangeMultiplier(multiplier) => {
+                player_stats.range_multiplier = *multiplier;
             }
         },
     );
 }
 
-#[derive(Resource, Reflect, Debug, Default)]
+#[derive(Resource, Reflect, Debug)]
 #[reflect(Resource)]
 struct PlayerStats {
-    pub attack_energy: f32,
+    /// Multiplies the energy of every discharge point.
+    pub energy_multiplier: f32,
+    /// Multiplies the range of every discharge point.
+    pub range_multiplier: f32,
+}
+
+impl Default for PlayerStats {
+    fn default() -> Self {
+        Self {
+            energy_multiplier: 1.0,
+            range_multiplier: 1.0,
+        }
+    }
 }
 
 #[derive(Event, Debug, Clone)]
 pub enum ChangePlayerStats {
-    SetAttackEnergy(f32),
+    SetEnergyMultiplier(f32),
+    SetRangeMultiplier(f32),
 }
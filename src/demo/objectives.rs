@@ -0,0 +1,245 @@
+//! Level objectives, see [`Objective`].
+//!
+//! Every frame the current level's objectives are measured against the run.
+//! Met objectives stay met, and once enough of them are met the level is
+//! complete and [`CompleteTheGame`] is sent.
+
+use std::time::Duration;
+
+use bevy::{color::palettes::css::GOLD, diagnostic::DiagnosticsStore, ecs::spawn::SpawnIter};
+use serde::{Deserialize, Serialize};
+
+use crate::{menus::CompleteTheGame, prelude::*, theme::palette::PURCHASE_ROW_BACKGROUND};
+
+use super::{
+    currency::{Currency, NumberFormat},
+    gameplay::PollutionMeter,
+    level::{CurrentLevel, catalog::Objective},
+    ui::{
+        collect_rate::DUST_COLLECT_RATE_DIAGNOSTIC,
+        inventory::Inventory,
+        purchase::{ShopState, catalog::UpgradeCatalog},
+    },
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<ObjectiveProgress>();
+
+    app.add_systems(
+        Update,
+        track_objectives
+            .in_set(AppSystems::Update)
+            .run_if(in_state(Screen::Gameplay)),
+    );
+    app.add_systems(
+        Update,
+        update_objective_panel.run_if(in_state(Screen::Gameplay)),
+    );
+}
+
+/// The current run's progress on the level's objectives.
+#[derive(Resource, Serialize, Deserialize, Debug, Clone, Default)]
+pub struct ObjectiveProgress {
+    /// Seconds played this run, pauses excluded.
+    pub elapsed: f32,
+    /// Whether each of the level's objectives was met, by index.
+    met: Vec<bool>,
+    /// Seconds played when the level was completed.
+    completed_at: Option<f32>,
+    /// Latest measurement of each objective, by index.
+    #[serde(skip)]
+    statuses: Vec<ObjectiveStatus>,
+}
+
+impl ObjectiveProgress {
+    pub fn is_met(&self, index: usize) -> bool {
+        self.met.get(index).copied().unwrap_or_default()
+    }
+
+    pub fn completed_at(&self) -> Option<f32> {
+        self.completed_at
+    }
+
+    /// The met objectives among `objectives`, in order.
+    pub fn met_objectives(&self, objectives: &[Objective]) -> Vec<Objective> {
+        objectives
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| self.is_met(*i))
+            .map(|(_, objective)| objective.clone())
+            .collect()
+    }
+}
+
+/// How far an objective is, `current` counts towards `target`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ObjectiveStatus {
+    pub current: f64,
+    pub target: f64,
+    /// Can't be met anymore this run.
+    pub failed: bool,
+}
+
+impl ObjectiveStatus {
+    fn is_met(&self) -> bool {
+        !self.failed && self.current >= self.target
+    }
+}
+
+/// What objectives are measured against.
+struct RunSnapshot<'a> {
+    collect_rate: f64,
+    collected: Currency,
+    elapsed: f32,
+    leaked: f32,
+    shop_state: &'a ShopState,
+}
+
+impl Objective {
+    fn status(&self, run: &RunSnapshot) -> ObjectiveStatus {
+        match self {
+            Self::CollectRate(rate) => ObjectiveStatus {
+                current: run.collect_rate,
+                target: *rate,
+                failed: false,
+            },
+            Self::Collected(amount) => ObjectiveStatus {
+                current: run.collected.to_f64(),
+                target: amount.to_f64(),
+                failed: false,
+            },
+            Self::Survive {
+                minutes,
+                max_leaked,
+            } => ObjectiveStatus {
+                current: run.elapsed as f64 / 60.0,
+                target: *minutes as f64,
+                failed: run.leaked >= *max_leaked,
+            },
+            Self::UpgradeLevel { id, level } => ObjectiveStatus {
+                current: run.shop_state.level(id) as f64,
+                target: *level as f64,
+                failed: false,
+            },
+        }
+    }
+
+    fn progress_text(&self, status: &ObjectiveStatus, format: NumberFormat) -> String {
+        if status.failed {
+            return "failed".to_string();
+        }
+        let current = status.current.min(status.target);
+        match self {
+            Self::CollectRate(_) | Self::Collected(_) => format!(
+                "{}/{}",
+                format.format(Currency::from_f64(current)),
+                format.format(Currency::from_f64(status.target))
+            ),
+            Self::Survive { .. } => format!("{current:.1}/{}", status.target),
+            Self::UpgradeLevel { .. } => format!("{current:.0}/{}", status.target),
+        }
+    }
+}
+
+fn track_objectives(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut progress: ResMut<ObjectiveProgress>,
+    level: Res<CurrentLevel>,
+    diagnostics: Res<DiagnosticsStore>,
+    inventory: Res<Inventory>,
+    meter: Res<PollutionMeter>,
+    shop_state: Res<ShopState>,
+    catalog: Res<UpgradeCatalog>,
+    format: Res<NumberFormat>,
+) {
+    let progress = &mut *progress;
+    progress.elapsed += time.delta_secs();
+    let run = RunSnapshot {
+        collect_rate: diagnostics
+            .get(&DUST_COLLECT_RATE_DIAGNOSTIC)
+            .and_then(|rate| rate.smoothed())
+            .unwrap_or_default(),
+        collected: inventory.collected,
+        elapsed: progress.elapsed,
+        leaked: meter.total_leaked(),
+        shop_state: &shop_state,
+    };
+
+    progress.met.resize(level.objectives.len(), false);
+    progress.statuses.clear();
+    for (objective, met) in level.objectives.iter().zip(&mut progress.met) {
+        let status = objective.status(&run);
+        if !*met && status.is_met() {
+            *met = true;
+            info!("Objective met: {}", objective.describe(&catalog, *format));
+        }
+        progress.statuses.push(status);
+    }
+
+    let met = progress.met.iter().filter(|met| **met).count();
+    if progress.completed_at.is_none() && met >= level.objectives_to_complete() {
+        progress.completed_at = Some(progress.elapsed);
+        commands.send_event(CompleteTheGame {
+            objectives: progress.met_objectives(&level.objectives),
+            elapsed: Duration::from_secs_f32(progress.elapsed),
+        });
+    }
+}
+
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+struct ObjectiveText(usize);
+
+/// One line per objective of the level, with its progress.
+pub fn objective_panel(objectives: &[Objective]) -> impl Bundle {
+    let count = objectives.len();
+    (
+        Name::new("Objective Panel"),
+        Node {
+            width: Val::Percent(100.0),
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Start,
+            padding: UiRect::axes(Val::Px(10.0), Val::Px(5.0)),
+            ..default()
+        },
+        BorderRadius::all(Val::Px(5.0)),
+        BackgroundColor(PURCHASE_ROW_BACKGROUND),
+        StateScoped(Screen::Gameplay),
+        Children::spawn(SpawnIter((0..count).map(|i| {
+            (
+                Name::new("Objective"),
+                Text::default(),
+                ObjectiveText(i),
+                TextColor::from(Color::BLACK),
+                TextFont::from_font_size(18.0),
+            )
+        }))),
+    )
+}
+
+fn update_objective_panel(
+    progress: Res<ObjectiveProgress>,
+    level: Res<CurrentLevel>,
+    catalog: Res<UpgradeCatalog>,
+    format: Res<NumberFormat>,
+    mut texts: Query<(&mut Text, &mut TextColor, &ObjectiveText)>,
+) {
+    for (mut text, mut color, ObjectiveText(i)) in &mut texts {
+        let Some(objective) = level.objectives.get(*i) else {
+            continue;
+        };
+        let status = progress.statuses.get(*i).copied().unwrap_or_default();
+        let met = progress.is_met(*i);
+        text.0 = if met {
+            format!("[x] {}", objective.describe(&catalog, *format))
+        } else {
+            format!(
+                "[ ] {} ({})",
+                objective.describe(&catalog, *format),
+                objective.progress_text(&status, *format)
+            )
+        };
+        color.0 = if met { GOLD.into() } else { Color::BLACK };
+    }
+}
@@ -0,0 +1,113 @@
+//! Dust data earned while the game was closed.
+//!
+//! When a saved run is continued, the time since it was saved is paid out at
+//! the run's last collect rate plus the passive data center income, reduced by
+//! [`OfflineProgress`]. The "While you were away" menu shows the estimate and
+//! the dust data is credited when it is closed.
+
+use crate::{menus::Menu, prelude::*};
+
+use super::{
+    currency::Currency,
+    ui::inventory::{DustDataChanged, Inventory},
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<OfflineProgress>();
+
+    app.add_systems(OnExit(Menu::OfflineProgress), collect_offline_earnings);
+}
+
+/// Tuning for offline progress.
+#[derive(Resource, Debug, Clone)]
+pub struct OfflineProgress {
+    /// Longer absences are only paid up to this many seconds.
+    pub max_away_secs: f64,
+    /// Fraction of the online income that is earned while away.
+    pub efficiency: f64,
+    /// Shorter absences (e.g. a quick restart) earn nothing.
+    pub min_away_secs: f64,
+}
+
+impl Default for OfflineProgress {
+    fn default() -> Self {
+        Self {
+            max_away_secs: 8.0 * 60.0 * 60.0,
+            efficiency: 0.5,
+            min_away_secs: 60.0,
+        }
+    }
+}
+
+/// Offline income waiting to be collected from the "While you were away" menu.
+#[derive(Resource, Debug, Clone)]
+pub struct OfflineEarnings {
+    /// Wall-clock time since the run was saved.
+    pub away_secs: f64,
+    /// The part of `away_secs` that was paid, after the cap.
+    pub paid_secs: f64,
+    /// Smoothed collect rate when the run was saved, in dust per second.
+    pub collect_rate: f64,
+    /// Data center income, in dust data per second.
+    pub passive_rate: f64,
+    pub efficiency: f64,
+    pub dust_data: Currency,
+}
+
+impl OfflineProgress {
+    /// Estimate the earnings of a run saved at `saved_at` (Unix seconds),
+    /// `None` if the player wasn't away long enough to earn anything.
+    pub fn estimate(
+        &self,
+        saved_at: f64,
+        now: f64,
+        collect_rate: f64,
+        passive_rate: f64,
+    ) -> Option<OfflineEarnings> {
+        let away_secs = now - saved_at;
+        // also rejects a clock that went backwards
+        if away_secs.is_nan() || away_secs < self.min_away_secs {
+            return None;
+        }
+        let paid_secs = away_secs.min(self.max_away_secs);
+        let dust_data =
+            ((collect_rate.max(0.0) + passive_rate) * paid_secs * self.efficiency).floor();
+        if dust_data < 1.0 {
+            return None;
+        }
+        Some(OfflineEarnings {
+            away_secs,
+            paid_secs,
+            collect_rate,
+            passive_rate,
+            efficiency: self.efficiency,
+            dust_data: Currency::from_f64(dust_data),
+        })
+    }
+}
+
+/// Format seconds as e.g. `2h 05m` or `3m 20s`.
+pub fn format_duration(secs: f64) -> String {
+    let secs = secs.max(0.0) as u64;
+    let (hours, minutes, seconds) = (secs / 3600, secs / 60 % 60, secs % 60);
+    if hours > 0 {
+        format!("{hours}h {minutes:02}m")
+    } else {
+        format!("{minutes}m {seconds:02}s")
+    }
+}
+
+fn collect_offline_earnings(
+    mut commands: Commands,
+    earnings: Option<Res<OfflineEarnings>>,
+    mut inventory: ResMut<Inventory>,
+    mut data_changed: EventWriter<DustDataChanged>,
+) {
+    let Some(earnings) = earnings else {
+        return;
+    };
+    info!("Collected {} offline dust data", earnings.dust_data);
+    inventory.dust_data += earnings.dust_data;
+    data_changed.write(DustDataChanged::Earned(earnings.dust_data));
+    commands.remove_resource::<OfflineEarnings>();
+}
@@ -0,0 +1,298 @@
+//! Prestige: trade a completed run for insight, spent on meta upgrades that
+//! carry over to every later run.
+//!
+//! Meta upgrades are declared in one table, see [`MetaUpgrade::spec`]. Their
+//! bonuses are applied to the starting stats of a new run and to every
+//! Research Lab upgrade bought during it, so buying a stat doesn't undo them.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{prelude::*, storage};
+
+use super::{
+    ChangePlayerStats, PlayerStats,
+    currency::Currency,
+    gameplay::{DustSpawner, Power, SetDustSpawnStats, SetPowerStats},
+    save::{DiscardRun, SaveGame},
+    ui::{
+        inventory::Inventory,
+        purchase::catalog::{CostCurve, UpgradeTarget},
+    },
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.insert_resource(MetaProgress::load());
+}
+
+const META_KEY: &str = "meta";
+/// Bump when [`MetaProgress`] changes shape.
+const META_VERSION: u32 = 2;
+/// Dust data collected per insight, before the square root.
+const DUST_PER_INSIGHT: f64 = 1000.0;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum MetaUpgrade {
+    Capacitors,
+    Dynamo,
+    Overcharge,
+    LongArcs,
+    DirtyAir,
+    SeedData,
+}
+
+pub struct MetaUpgradeSpec {
+    pub name: &'static str,
+    pub tips: &'static str,
+    /// Insight price of buying level `n + 1`.
+    pub cost: CostCurve,
+    pub max_level: usize,
+    /// Must have at least one level before this one can be bought.
+    pub requires: Option<MetaUpgrade>,
+}
+
+impl MetaUpgrade {
+    pub const ALL: [Self; 6] = [
+        Self::Capacitors,
+        Self::Dynamo,
+        Self::Overcharge,
+        Self::LongArcs,
+        Self::DirtyAir,
+        Self::SeedData,
+    ];
+
+    pub fn spec(&self) -> MetaUpgradeSpec {
+        let base = MetaUpgradeSpec {
+            name: "",
+            tips: "",
+            cost: CostCurve::exponential(1.0, 2.0),
+            max_level: 5,
+            requires: None,
+        };
+        match self {
+            Self::Capacitors => MetaUpgradeSpec {
+                name: "Capacitors",
+                tips: "+2 max energy",
+                ..base
+            },
+            Self::Dynamo => MetaUpgradeSpec {
+                name: "Dynamo",
+                tips: "+10% energy recovery",
+                ..base
+            },
+            Self::Overcharge => MetaUpgradeSpec {
+                name: "Overcharge",
+                tips: "+10% discharge energy",
+                cost: CostCurve::exponential(2.0, 2.0),
+                requires: Some(Self::Capacitors),
+                ..base
+            },
+            Self::LongArcs => MetaUpgradeSpec {
+                name: "Long Arcs",
+                tips: "+5% discharge range",
+                cost: CostCurve::exponential(2.0, 2.0),
+                requires: Some(Self::Overcharge),
+                ..base
+            },
+            Self::DirtyAir => MetaUpgradeSpec {
+                name: "Dirty Air",
+                tips: "+0.5 pollution rate",
+                max_level: 4,
+                ..base
+            },
+            Self::SeedData => MetaUpgradeSpec {
+                name: "Seed Data",
+                tips: "+25 starting dust data",
+                max_level: 4,
+                requires: Some(Self::DirtyAir),
+                ..base
+            },
+        }
+    }
+}
+
+/// Everything that survives a prestige, stored apart from the run's save.
+#[derive(Resource, Serialize, Deserialize, Debug, Clone)]
+pub struct MetaProgress {
+    version: u32,
+    /// Unspent insight, a whole number before version 2.
+    pub insight: Currency,
+    pub prestiges: u32,
+    levels: BTreeMap<MetaUpgrade, usize>,
+}
+
+impl Default for MetaProgress {
+    fn default() -> Self {
+        Self {
+            version: META_VERSION,
+            insight: Currency::ZERO,
+            prestiges: 0,
+            levels: BTreeMap::new(),
+        }
+    }
+}
+
+impl MetaProgress {
+    /// Read the stored meta progress, a fresh one if there is none.
+    fn load() -> Self {
+        let Some(contents) = storage::load(META_KEY) else {
+            return Self::default();
+        };
+        match ron::from_str::<Self>(&contents) {
+            Ok(meta) if meta.version <= META_VERSION => meta,
+            Ok(meta) => {
+                warn!(
+                    "Ignoring meta progress with unsupported version {}",
+                    meta.version
+                );
+                Self::default()
+            }
+            Err(error) => {
+                warn!("Ignoring unreadable meta progress: {error}");
+                Self::default()
+            }
+        }
+    }
+
+    fn store(&self) {
+        let result = ron::ser::to_string_pretty(self, default())
+            .map_err(std::io::Error::other)
+            .and_then(|contents| storage::save(META_KEY, &contents));
+        if let Err(error) = result {
+            error!("Could not save meta progress: {error}");
+        }
+    }
+
+    pub fn level(&self, upgrade: MetaUpgrade) -> usize {
+        self.levels.get(&upgrade).copied().unwrap_or_default()
+    }
+
+    pub fn cost(&self, upgrade: MetaUpgrade) -> Currency {
+        upgrade.spec().cost.value(self.level(upgrade))
+    }
+
+    pub fn is_maxed(&self, upgrade: MetaUpgrade) -> bool {
+        self.level(upgrade) >= upgrade.spec().max_level
+    }
+
+    /// Whether the upgrade's prerequisite was bought.
+    pub fn is_unlocked(&self, upgrade: MetaUpgrade) -> bool {
+        upgrade
+            .spec()
+            .requires
+            .is_none_or(|required| self.level(required) > 0)
+    }
+
+    /// Buy the next level of a meta upgrade, returns whether it was possible.
+    pub fn purchase(&mut self, upgrade: MetaUpgrade) -> bool {
+        let cost = self.cost(upgrade);
+        if !self.is_unlocked(upgrade) || self.is_maxed(upgrade) {
+            return false;
+        }
+        let Some(left) = self.insight.checked_sub(cost) else {
+            return false;
+        };
+        self.insight = left;
+        *self.levels.entry(upgrade).or_default() += 1;
+        self.store();
+        true
+    }
+
+    /// Insight earned by resetting a run that collected `collected` dust data.
+    pub fn insight_for(collected: Currency) -> Currency {
+        collected.scale(DUST_PER_INSIGHT.recip()).powf(0.5).floor()
+    }
+
+    /// Bank the insight of the current run and forget the run.
+    pub fn prestige(&mut self, inventory: &Inventory, commands: &mut Commands) {
+        self.insight += Self::insight_for(inventory.collected);
+        self.prestiges += 1;
+        self.store();
+        SaveGame::delete();
+        commands.insert_resource(DiscardRun);
+        info!("Prestiged, {} insight", self.insight);
+    }
+
+    pub fn bonuses(&self) -> MetaBonuses {
+        let level = |upgrade| self.level(upgrade) as f32;
+        MetaBonuses {
+            power_max: 2.0 * level(MetaUpgrade::Capacitors),
+            regen_multiplier: 1.0 + 0.1 * level(MetaUpgrade::Dynamo),
+            energy_multiplier: 1.0 + 0.1 * level(MetaUpgrade::Overcharge),
+            range_multiplier: 1.0 + 0.05 * level(MetaUpgrade::LongArcs),
+            spawn_speed: 0.5 * level(MetaUpgrade::DirtyAir),
+            dust_data: 25 * self.level(MetaUpgrade::SeedData) as u32,
+        }
+    }
+}
+
+/// What the bought meta upgrades add to a run.
+#[derive(Debug, Clone, Copy)]
+pub struct MetaBonuses {
+    pub power_max: f32,
+    pub regen_multiplier: f32,
+    pub energy_multiplier: f32,
+    pub range_multiplier: f32,
+    pub spawn_speed: f32,
+    /// Dust data to start a run with.
+    pub dust_data: u32,
+}
+
+impl Default for MetaBonuses {
+    fn default() -> Self {
+        Self {
+            power_max: 0.0,
+            regen_multiplier: 1.0,
+            energy_multiplier: 1.0,
+            range_multiplier: 1.0,
+            spawn_speed: 0.0,
+            dust_data: 0,
+        }
+    }
+}
+
+impl MetaBonuses {
+    /// The value a Research Lab upgrade of `target` sets its stat to.
+    pub fn modify(&self, target: UpgradeTarget, value: f32) -> f32 {
+        match target {
+            UpgradeTarget::AttackEnergy => value * self.energy_multiplier,
+            UpgradeTarget::AttackRange => value * self.range_multiplier,
+            UpgradeTarget::EnergyRecovery => value * self.regen_multiplier,
+            UpgradeTarget::EnergyCapacity => value + self.power_max,
+            UpgradeTarget::PollutionRate => value + self.spawn_speed,
+            UpgradeTarget::DischargePoints(_)
+            | UpgradeTarget::UnlockTargeting(_)
+            | UpgradeTarget::UnlockAutoBuyer => value,
+        }
+    }
+}
+
+/// Give a new run its meta bonuses, once the level is spawned. A restored run
+/// overwrites these with its saved stats, which already include them.
+pub(super) fn apply_starting_bonuses(
+    mut commands: Commands,
+    meta: Res<MetaProgress>,
+    power: Res<Power>,
+    player_stats: Res<PlayerStats>,
+    dust_spawner: Single<&DustSpawner>,
+    mut inventory: ResMut<Inventory>,
+) {
+    let bonuses = meta.bonuses();
+    let power_max = power.max() + bonuses.power_max;
+    commands.trigger(SetPowerStats::PowerMax(power_max));
+    commands.trigger(SetPowerStats::RegenSpeed(
+        power.regen_speed() * bonuses.regen_multiplier,
+    ));
+    commands.trigger(SetPowerStats::Current(power_max));
+    commands.trigger(ChangePlayerStats::SetEnergyMultiplier(
+        player_stats.energy_multiplier * bonuses.energy_multiplier,
+    ));
+    commands.trigger(ChangePlayerStats::SetRangeMultiplier(
+        player_stats.range_multiplier * bonuses.range_multiplier,
+    ));
+    commands.trigger(SetDustSpawnStats::SpawnSpeed(
+        dust_spawner.speed() + bonuses.spawn_speed,
+    ));
+    inventory.dust_data += Currency::from(bonuses.dust_data);
+}
@@ -0,0 +1,381 @@
+//! Save and restore a run.
+//!
+//! The run is saved on an interval, when leaving gameplay and when the app
+//! exits. Every gameplay session starts from fresh resources; "Continue" in the
+//! main menu then re-applies the save through the same events the Research Lab
+//! uses, and pays out [offline progress](super::offline).
+
+use std::collections::{BTreeSet, HashMap};
+
+use bevy::diagnostic::DiagnosticsStore;
+use serde::{Deserialize, Serialize};
+
+use crate::{menus::Menu, prelude::*, storage};
+
+use super::{
+    ChangePlayerStats, PlayerStats,
+    currency::Currency,
+    gameplay::{
+        Attacker, AttackerStats, BASE_ATTACK_ENERGY, DamageType, DustSpawner, PollutionMeter,
+        Power, SetDustSpawnStats, SetPowerStats, SpawnAttacker, TargetingStrategy,
+        UnlockedStrategies,
+    },
+    level::{CurrentLevel, spawn_level},
+    objectives::ObjectiveProgress,
+    offline::OfflineProgress,
+    prestige::{MetaProgress, apply_starting_bonuses},
+    statistics::{Counters, Statistics},
+    ui::{
+        collect_rate::DUST_COLLECT_RATE_DIAGNOSTIC,
+        inventory::Inventory,
+        purchase::{ShopState, auto_buy::AutoBuyer, catalog::UpgradeCatalog},
+    },
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<AutosaveTimer>();
+
+    app.add_systems(
+        OnEnter(Screen::Gameplay),
+        (
+            reset_run.before(spawn_level),
+            apply_starting_bonuses.after(spawn_level),
+            restore_run.after(apply_starting_bonuses),
+        ),
+    );
+    app.add_systems(
+        Update,
+        tick_autosave_timer
+            .in_set(AppSystems::TickTimers)
+            .run_if(in_state(Screen::Gameplay)),
+    );
+    app.add_systems(
+        Update,
+        save_run
+            .in_set(AppSystems::Cleanup)
+            .run_if(in_state(Screen::Gameplay).and(autosave_due)),
+    );
+    app.add_systems(OnExit(Screen::Gameplay), save_run);
+    // also save when the window is closed mid-run
+    app.add_systems(
+        Last,
+        save_run.run_if(in_state(Screen::Gameplay).and(on_event::<AppExit>)),
+    );
+    // a lost run can't be continued
+    app.add_systems(OnEnter(Menu::RunLost), delete_save);
+}
+
+const SAVE_KEY: &str = "save";
+/// Bump when [`SaveGame`] changes shape. Older versions still load as long as
+/// the new fields have serde defaults.
+const SAVE_VERSION: u32 = 13;
+const AUTOSAVE_INTERVAL_SECS: f32 = 30.0;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SaveGame {
+    version: u32,
+    /// Id of the level, empty before version 8 when there was only one.
+    #[serde(default)]
+    pub level: String,
+    /// Whole numbers before version 12, which still load as [`Currency`].
+    dust_data: Currency,
+    /// Research Lab levels by upgrade id.
+    upgrades: HashMap<String, usize>,
+    power: f32,
+    power_max: f32,
+    power_regen_speed: f32,
+    #[serde(default)]
+    energy_multiplier: f32,
+    #[serde(default)]
+    discharge_points: Vec<SavedAttacker>,
+    dust_spawn_speed: f32,
+    /// Unix seconds, `None` for saves written before version 2.
+    #[serde(default)]
+    saved_at: Option<f64>,
+    /// Smoothed dust collect rate at the time of saving.
+    #[serde(default)]
+    collect_rate: f64,
+    /// Level of the pollution meter, empty before version 6.
+    #[serde(default)]
+    pollution: f32,
+    /// Dust data collected from dust this run, 0 before version 7.
+    #[serde(default)]
+    collected: Currency,
+    /// Pollution leaked this run, 0 before version 9.
+    #[serde(default)]
+    leaked: f32,
+    /// Progress on the level's objectives, empty before version 9.
+    #[serde(default)]
+    objectives: ObjectiveProgress,
+    /// The run's statistics, empty before version 10.
+    #[serde(default)]
+    statistics: Counters,
+    /// Ids of the upgrades switched to auto-buy, empty before version 11.
+    #[serde(default)]
+    auto_buy: BTreeSet<String>,
+    /// Before version 5 discharge points shared one attack energy and were
+    /// saved as parallel lists, read by [`SaveGame::migrate`].
+    #[serde(default, skip_serializing)]
+    attack_energy: f32,
+    #[serde(default, skip_serializing)]
+    attackers: Vec<[f32; 2]>,
+    /// Empty before version 3, when every attacker was lightning.
+    #[serde(default, skip_serializing)]
+    attacker_damage_types: Vec<DamageType>,
+    /// Empty before version 4.
+    #[serde(default, skip_serializing)]
+    attacker_strategies: Vec<TargetingStrategy>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct SavedAttacker {
+    pos: [f32; 2],
+    damage_type: DamageType,
+    strategy: TargetingStrategy,
+    stats: AttackerStats,
+}
+
+impl SaveGame {
+    /// Read the stored save, `None` if there is none or it can't be used.
+    pub fn load() -> Option<Self> {
+        let contents = storage::load(SAVE_KEY)?;
+        match ron::from_str::<Self>(&contents) {
+            Ok(mut save) if save.version <= SAVE_VERSION => {
+                save.migrate();
+                Some(save)
+            }
+            Ok(save) => {
+                warn!("Ignoring save with unsupported version {}", save.version);
+                None
+            }
+            Err(error) => {
+                warn!("Ignoring unreadable save: {error}");
+                None
+            }
+        }
+    }
+
+    /// Bring a save from an older version up to [`SAVE_VERSION`].
+    fn migrate(&mut self) {
+        if self.version < 5 {
+            self.energy_multiplier = self.attack_energy / BASE_ATTACK_ENERGY;
+            self.discharge_points = self
+                .attackers
+                .iter()
+                .enumerate()
+                .map(|(i, pos)| {
+                    let damage_type = self
+                        .attacker_damage_types
+                        .get(i)
+                        .copied()
+                        .unwrap_or_default();
+                    SavedAttacker {
+                        pos: *pos,
+                        damage_type,
+                        strategy: self.attacker_strategies.get(i).copied().unwrap_or_default(),
+                        stats: AttackerStats::new(damage_type),
+                    }
+                })
+                .collect();
+        }
+        if self.version < 13 {
+            // lightning used to stop after a few hops, now only its energy ends it
+            for point in &mut self.discharge_points {
+                point.stats.chain_limit = AttackerStats::new(point.damage_type).chain_limit;
+            }
+        }
+        self.version = SAVE_VERSION;
+    }
+
+    fn store(&self) {
+        let result = ron::ser::to_string_pretty(self, default())
+            .map_err(std::io::Error::other)
+            .and_then(|contents| storage::save(SAVE_KEY, &contents));
+        if let Err(error) = result {
+            error!("Could not save the run: {error}");
+        }
+    }
+
+    pub fn delete() {
+        if let Err(error) = storage::remove(SAVE_KEY) {
+            error!("Could not delete the save: {error}");
+        }
+    }
+}
+
+/// Don't save the current run anymore, e.g. after trading it in for a
+/// [prestige](super::prestige).
+#[derive(Resource, Debug)]
+pub struct DiscardRun;
+
+/// A save to restore when gameplay starts, inserted by the "Continue" button.
+#[derive(Resource, Debug)]
+pub struct PendingRestore(pub SaveGame);
+
+#[derive(Resource, Debug)]
+struct AutosaveTimer(Timer);
+
+impl Default for AutosaveTimer {
+    fn default() -> Self {
+        Self(Timer::from_seconds(
+            AUTOSAVE_INTERVAL_SECS,
+            TimerMode::Repeating,
+        ))
+    }
+}
+
+fn tick_autosave_timer(mut timer: ResMut<AutosaveTimer>, time: Res<Time>) {
+    timer.0.tick(time.delta());
+}
+
+fn autosave_due(timer: Res<AutosaveTimer>) -> bool {
+    timer.0.just_finished()
+}
+
+fn save_run(
+    inventory: Res<Inventory>,
+    shop_state: Res<ShopState>,
+    power: Res<Power>,
+    player_stats: Res<PlayerStats>,
+    meter: Res<PollutionMeter>,
+    level: Res<CurrentLevel>,
+    objectives: Res<ObjectiveProgress>,
+    statistics: Res<Statistics>,
+    auto_buyer: Res<AutoBuyer>,
+    discard: Option<Res<DiscardRun>>,
+    attackers: Query<(&Transform, &Attacker, &TargetingStrategy, &AttackerStats)>,
+    dust_spawner: Query<&DustSpawner>,
+    diagnostics: Res<DiagnosticsStore>,
+) {
+    let Ok(dust_spawner) = dust_spawner.single() else {
+        return; // Level isn't spawned, nothing to save
+    };
+    if level.is_lost(&meter) || discard.is_some() {
+        return; // The run is lost or traded in, see `delete_save`
+    }
+    SaveGame {
+        version: SAVE_VERSION,
+        level: level.id.clone(),
+        dust_data: inventory.dust_data,
+        upgrades: shop_state.levels().clone(),
+        power: power.current(),
+        power_max: power.max(),
+        power_regen_speed: power.regen_speed(),
+        energy_multiplier: player_stats.energy_multiplier,
+        discharge_points: attackers
+            .iter()
+            .map(|(transform, attacker, strategy, stats)| SavedAttacker {
+                pos: transform.translation.truncate().to_array(),
+                damage_type: attacker.damage_type(),
+                strategy: *strategy,
+                stats: *stats,
+            })
+            .collect(),
+        dust_spawn_speed: dust_spawner.speed(),
+        saved_at: Some(unix_time_secs()),
+        collect_rate: diagnostics
+            .get(&DUST_COLLECT_RATE_DIAGNOSTIC)
+            .and_then(|rate| rate.smoothed())
+            .unwrap_or_default(),
+        pollution: meter.level(),
+        collected: inventory.collected,
+        leaked: meter.total_leaked(),
+        objectives: objectives.clone(),
+        statistics: statistics.run.clone(),
+        auto_buy: auto_buyer.enabled().clone(),
+        attack_energy: 0.0,
+        attackers: Vec::new(),
+        attacker_damage_types: Vec::new(),
+        attacker_strategies: Vec::new(),
+    }
+    .store();
+    info!("Run saved");
+}
+
+fn delete_save() {
+    SaveGame::delete();
+    info!("Run lost, save deleted");
+}
+
+/// Start every gameplay session from the initial resources.
+fn reset_run(
+    mut commands: Commands,
+    mut statistics: ResMut<Statistics>,
+    mut autosave_timer: ResMut<AutosaveTimer>,
+) {
+    commands.remove_resource::<DiscardRun>();
+    commands.insert_resource(Inventory::default());
+    commands.insert_resource(ShopState::default());
+    commands.insert_resource(AutoBuyer::default());
+    commands.insert_resource(Power::default());
+    commands.insert_resource(PlayerStats::default());
+    commands.insert_resource(UnlockedStrategies::default());
+    commands.insert_resource(PollutionMeter::default());
+    commands.insert_resource(ObjectiveProgress::default());
+    statistics.run = Counters::default();
+    autosave_timer.0.reset();
+}
+
+fn restore_run(
+    mut commands: Commands,
+    pending: Option<Res<PendingRestore>>,
+    mut inventory: ResMut<Inventory>,
+    mut shop_state: ResMut<ShopState>,
+    mut meter: ResMut<PollutionMeter>,
+    mut statistics: ResMut<Statistics>,
+    mut auto_buyer: ResMut<AutoBuyer>,
+    catalog: Res<UpgradeCatalog>,
+    meta: Res<MetaProgress>,
+    offline_progress: Res<OfflineProgress>,
+    attackers: Query<Entity, With<Attacker>>,
+) {
+    let Some(pending) = pending else {
+        return;
+    };
+    let save = &pending.0;
+    info!("Restoring saved run");
+
+    inventory.dust_data = save.dust_data;
+    inventory.collected = save.collected;
+    meter.set_level(save.pollution);
+    meter.set_total_leaked(save.leaked);
+    commands.insert_resource(save.objectives.clone());
+    statistics.run = save.statistics.clone();
+    shop_state.set_levels(save.upgrades.clone());
+    auto_buyer.set_enabled(save.auto_buy.clone());
+    // brings back unlocks, the saved stats below take precedence
+    shop_state.reapply(&catalog, &meta.bonuses(), &mut commands);
+    commands.trigger(SetPowerStats::PowerMax(save.power_max));
+    commands.trigger(SetPowerStats::RegenSpeed(save.power_regen_speed));
+    commands.trigger(SetPowerStats::Current(save.power));
+    commands.trigger(ChangePlayerStats::SetEnergyMultiplier(
+        save.energy_multiplier,
+    ));
+    commands.trigger(SetDustSpawnStats::SpawnSpeed(save.dust_spawn_speed));
+
+    // replace the level's starting attacker with the saved ones
+    for attacker in &attackers {
+        commands.entity(attacker).despawn();
+    }
+    for attacker in &save.discharge_points {
+        commands.trigger(SpawnAttacker {
+            pos: Vec2::from_array(attacker.pos),
+            damage_type: attacker.damage_type,
+            strategy: attacker.strategy,
+            stats: Some(attacker.stats),
+        });
+    }
+
+    if let Some(earnings) = save.saved_at.and_then(|saved_at| {
+        offline_progress.estimate(
+            saved_at,
+            unix_time_secs(),
+            save.collect_rate,
+            inventory.passive_rate() * meter.income_factor() as f64,
+        )
+    }) {
+        commands.insert_resource(earnings);
+    }
+
+    commands.remove_resource::<PendingRestore>();
+}
@@ -0,0 +1,409 @@
+//! Headless balancing simulation.
+//!
+//! Runs the gameplay rules under [`MinimalPlugins`] with a fixed timestep and a
+//! seeded [`WyRand`], buys upgrades with a scripted policy and prints how the
+//! economy develops. Run with:
+//!
+//! ```sh
+//! cargo run --features headless -- simulate --minutes 20 --seed 42 --policy cheapest
+//! ```
+//!
+//! The upgrade catalog is read from `assets/data/upgrades.ron` (or `--catalog`)
+//! and the levels from `assets/data/levels.ron` (or `--levels`), so curves can
+//! be tuned without recompiling. `--level` picks the level by id, the first one
+//! by default.
+
+use std::time::Duration;
+
+use bevy::{diagnostic::DiagnosticsStore, state::app::StatesPlugin, time::TimeUpdateStrategy};
+use bevy_rand::plugin::EntropyPlugin;
+
+use crate::{menus::CompleteTheGame, prelude::*};
+
+use super::{
+    GameArea,
+    currency::{Currency, NumberFormat},
+    gameplay::{Attacker, PollutionMeter, dust_spawner},
+    level::{
+        CurrentLevel,
+        catalog::{LevelCatalog, LevelDef},
+        start_level,
+    },
+    objectives::ObjectiveProgress,
+    prestige::MetaBonuses,
+    ui::{
+        collect_rate::{DUST_COLLECT_RATE_DIAGNOSTIC, DUST_LEAK_RATE_DIAGNOSTIC},
+        inventory::Inventory,
+        purchase::{
+            BuyAmount, ShopState,
+            catalog::{UpgradeCatalog, UpgradeDef},
+        },
+    },
+};
+
+const USAGE: &str = "usage: simulate [--minutes N] [--seed N] [--tick-rate HZ] \
+                     [--report-every SECONDS] [--policy cheapest|round-robin] [--catalog PATH] \
+                     [--levels PATH] [--level ID]";
+
+/// Run the simulation if the first command line argument is `simulate`.
+pub fn run_from_args() -> Option<AppExit> {
+    let mut args = std::env::args().skip(1).peekable();
+    if args.peek().map(String::as_str) != Some("simulate") {
+        return None;
+    }
+    args.next();
+    let config = match SimulationConfig::parse(args) {
+        Ok(config) => config,
+        Err(error) => {
+            eprintln!("{error}\n{USAGE}");
+            return Some(AppExit::error());
+        }
+    };
+    let catalog = match std::fs::read(&config.catalog)
+        .map_err(Into::into)
+        .and_then(|bytes| UpgradeCatalog::from_ron(&bytes))
+    {
+        Ok(catalog) => catalog,
+        Err(error) => {
+            eprintln!("{}: {error}", config.catalog);
+            return Some(AppExit::error());
+        }
+    };
+    let levels = match std::fs::read(&config.levels)
+        .map_err(Into::into)
+        .and_then(|bytes| LevelCatalog::from_ron(&bytes))
+        .and_then(|levels| levels.check_upgrades(&catalog).map(|()| levels))
+    {
+        Ok(levels) => levels,
+        Err(error) => {
+            eprintln!("{}: {error}", config.levels);
+            return Some(AppExit::error());
+        }
+    };
+    let level = match &config.level {
+        Some(id) => levels.get(id),
+        None => levels.levels.first(),
+    };
+    let Some(level) = level.cloned() else {
+        eprintln!(
+            "{}: no level `{}`",
+            config.levels,
+            config.level.as_deref().unwrap_or("")
+        );
+        return Some(AppExit::error());
+    };
+    Some(run(config, catalog, level))
+}
+
+#[derive(Resource, Debug, Clone)]
+pub struct SimulationConfig {
+    /// Simulated play time in minutes.
+    pub minutes: f32,
+    pub seed: u64,
+    /// Simulated frames per second.
+    pub tick_rate: f32,
+    /// Seconds of simulated time between progress lines.
+    pub report_every: f32,
+    pub policy: PurchasePolicy,
+    /// Path of the upgrade catalog.
+    pub catalog: String,
+    /// Path of the level catalog.
+    pub levels: String,
+    /// Id of the simulated level, `None` for the first one.
+    pub level: Option<String>,
+}
+
+impl Default for SimulationConfig {
+    fn default() -> Self {
+        Self {
+            minutes: 20.0,
+            seed: 0,
+            tick_rate: 60.0,
+            report_every: 60.0,
+            policy: PurchasePolicy::Cheapest,
+            catalog: "assets/data/upgrades.ron".to_string(),
+            levels: "assets/data/levels.ron".to_string(),
+            level: None,
+        }
+    }
+}
+
+impl SimulationConfig {
+    fn parse(mut args: impl Iterator<Item = String>) -> Result<Self, String> {
+        let mut config = Self::default();
+        while let Some(flag) = args.next() {
+            let value = args
+                .next()
+                .ok_or_else(|| format!("missing value for `{flag}`"))?;
+            let invalid = || format!("invalid value `{value}` for `{flag}`");
+            match flag.as_str() {
+                "--minutes" => config.minutes = value.parse().map_err(|_| invalid())?,
+                "--seed" => config.seed = value.parse().map_err(|_| invalid())?,
+                "--tick-rate" => config.tick_rate = value.parse().map_err(|_| invalid())?,
+                "--report-every" => config.report_every = value.parse().map_err(|_| invalid())?,
+                "--policy" => {
+                    config.policy = match value.as_str() {
+                        "cheapest" => PurchasePolicy::Cheapest,
+                        "round-robin" => PurchasePolicy::RoundRobin,
+                        _ => return Err(format!("unknown policy `{value}`")),
+                    }
+                }
+                "--catalog" => config.catalog = value,
+                "--levels" => config.levels = value,
+                "--level" => config.level = Some(value),
+                _ => return Err(format!("unknown flag `{flag}`")),
+            }
+        }
+        if config.tick_rate <= 0.0 || config.report_every <= 0.0 {
+            return Err("`--tick-rate` and `--report-every` must be positive".to_string());
+        }
+        Ok(config)
+    }
+}
+
+/// How the simulated player spends dust data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PurchasePolicy {
+    /// Always buy the cheapest upgrade as soon as it is affordable.
+    Cheapest,
+    /// Buy each upgrade in turn, saving up for the next one in the list that
+    /// is unlocked and not maxed.
+    RoundRobin,
+}
+
+#[derive(Resource, Debug, Default)]
+struct SimulationReport {
+    /// Total dust data spent in the shop.
+    spent: Currency,
+    /// Index into the upgrades the level sells, for [`PurchasePolicy::RoundRobin`].
+    next_item: usize,
+    /// Simulated seconds until the level's loss condition was met.
+    lost_at: Option<f32>,
+    next_report: f32,
+}
+
+impl SimulationReport {
+    fn earned(&self, inventory: &Inventory) -> Currency {
+        inventory.dust_data + self.spent
+    }
+}
+
+pub fn run(config: SimulationConfig, catalog: UpgradeCatalog, level: LevelDef) -> AppExit {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        AssetPlugin::default(),
+        StatesPlugin,
+        EntropyPlugin::<WyRand>::with_seed(config.seed.to_le_bytes()),
+    ));
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f32(
+        1.0 / config.tick_rate,
+    )));
+
+    // Nothing is loaded, but the gameplay plugins register their collections.
+    app.insert_state(AssetsState::Done);
+    app.add_loading_state(LoadingState::new(AssetsState::Loading));
+    app.insert_state(Screen::Gameplay);
+    app.add_plugins((
+        crate::system_sets,
+        super::rules_plugin,
+        crate::visual_effect::plugin,
+    ));
+    app.add_event::<CompleteTheGame>();
+    app.insert_resource(catalog);
+    app.insert_resource(CurrentLevel(level));
+
+    app.insert_resource(SimulationReport {
+        next_report: config.report_every,
+        ..default()
+    });
+    app.insert_resource(config.clone());
+    app.add_systems(Startup, spawn_simulation_level);
+    app.add_systems(
+        Update,
+        (buy_upgrades, arrange_attackers, report_progress)
+            .chain()
+            .after(AppSystems::Cleanup),
+    );
+
+    println!(
+        "Simulating {} minutes of {} at {} Hz, seed {}, policy {:?}",
+        config.minutes,
+        app.world().resource::<CurrentLevel>().id,
+        config.tick_rate,
+        config.seed,
+        config.policy
+    );
+    app.finish();
+    app.cleanup();
+    let frames = (config.minutes * 60.0 * config.tick_rate).ceil() as u64;
+    for _ in 0..frames {
+        app.update();
+    }
+
+    print_summary(app.world());
+    AppExit::Success
+}
+
+fn spawn_simulation_level(
+    mut commands: Commands,
+    level: Res<CurrentLevel>,
+    mut inventory: ResMut<Inventory>,
+    mut entropy: GlobalEntropy<WyRand>,
+) {
+    commands.spawn((
+        Name::new("Level"),
+        StateScoped(Screen::Gameplay),
+        children![dust_spawner(level.spawn_speed, entropy.fork_rng())],
+    ));
+    start_level(&level, &mut inventory, &mut commands);
+}
+
+fn buy_upgrades(
+    mut commands: Commands,
+    config: Res<SimulationConfig>,
+    mut report: ResMut<SimulationReport>,
+    mut inventory: ResMut<Inventory>,
+    mut shop_state: ResMut<ShopState>,
+    catalog: Res<UpgradeCatalog>,
+    level: Res<CurrentLevel>,
+) {
+    let upgrades: Vec<&UpgradeDef> = catalog
+        .upgrades
+        .iter()
+        .filter(|upgrade| level.allows(&upgrade.id))
+        .collect();
+    loop {
+        let available = |i: &usize| shop_state.is_available(upgrades[*i], inventory.collected);
+        let item = match config.policy {
+            PurchasePolicy::Cheapest => (0..upgrades.len())
+                .filter(available)
+                .min_by_key(|i| shop_state.cost(upgrades[*i])),
+            // skip what is locked or maxed instead of waiting on it forever
+            PurchasePolicy::RoundRobin => (0..upgrades.len())
+                .map(|offset| (report.next_item + offset) % upgrades.len())
+                .find(available),
+        };
+        let Some(item) = item else {
+            return;
+        };
+        let upgrade = upgrades[item];
+        let cost = shop_state.cost(upgrade);
+        // the simulation always starts without meta upgrades
+        if !shop_state.purchase(
+            upgrade,
+            BuyAmount::One,
+            &mut inventory,
+            &MetaBonuses::default(),
+            &mut commands,
+        ) {
+            return;
+        }
+        report.spent += cost;
+        report.next_item = (item + 1) % upgrades.len();
+    }
+}
+
+/// Spread the discharge points over the game area, like a player would.
+fn arrange_attackers(area: Res<GameArea>, mut attackers: Query<&mut Transform, With<Attacker>>) {
+    let count = attackers.iter().len();
+    if count == 0 {
+        return;
+    }
+    let cols = (count as f32).sqrt().ceil() as usize;
+    let rows = count.div_ceil(cols);
+    let cell = area.size() / Vec2::new(cols as f32, rows as f32);
+    for (i, mut transform) in attackers.iter_mut().enumerate() {
+        let slot = Vec2::new((i % cols) as f32 + 0.5, (i / cols) as f32 + 0.5);
+        let pos = area.min + cell * slot;
+        transform.translation = pos.extend(transform.translation.z);
+    }
+}
+
+fn report_progress(
+    time: Res<Time>,
+    config: Res<SimulationConfig>,
+    diagnostics: Res<DiagnosticsStore>,
+    inventory: Res<Inventory>,
+    shop_state: Res<ShopState>,
+    catalog: Res<UpgradeCatalog>,
+    meter: Res<PollutionMeter>,
+    level: Res<CurrentLevel>,
+    mut report: ResMut<SimulationReport>,
+) {
+    let elapsed = time.elapsed_secs();
+    let rate = collect_rate(&diagnostics);
+    // the simulation keeps going, to see how the economy would have continued
+    if report.lost_at.is_none() && level.is_lost(&meter) {
+        report.lost_at = Some(elapsed);
+    }
+    if elapsed < report.next_report {
+        return;
+    }
+    report.next_report += config.report_every;
+
+    let levels = catalog
+        .upgrades
+        .iter()
+        .map(|upgrade| format!("{}={}", upgrade.id, shop_state.level(&upgrade.id)))
+        .collect::<Vec<_>>()
+        .join(" ");
+    println!(
+        "{:>7.0}s  rate {:>6.1} dust/s  leak {:>5.1}/s  pollution {:>3.0}%  earned {:>8}  balance {:>7}  {}",
+        elapsed,
+        rate,
+        leak_rate(&diagnostics),
+        meter.fraction() * 100.0,
+        report.earned(&inventory),
+        inventory.dust_data,
+        levels
+    );
+}
+
+fn collect_rate(diagnostics: &DiagnosticsStore) -> f64 {
+    diagnostics
+        .get(&DUST_COLLECT_RATE_DIAGNOSTIC)
+        .and_then(|rate| rate.smoothed())
+        .unwrap_or_default()
+}
+
+fn leak_rate(diagnostics: &DiagnosticsStore) -> f64 {
+    diagnostics
+        .get(&DUST_LEAK_RATE_DIAGNOSTIC)
+        .and_then(|rate| rate.smoothed())
+        .unwrap_or_default()
+}
+
+fn print_summary(world: &World) {
+    let report = world.resource::<SimulationReport>();
+    let inventory = world.resource::<Inventory>();
+    let level = world.resource::<CurrentLevel>();
+    let progress = world.resource::<ObjectiveProgress>();
+    let catalog = world.resource::<UpgradeCatalog>();
+    let format = *world.resource::<NumberFormat>();
+    println!();
+    println!(
+        "Final collect rate: {:.1} dust/s",
+        collect_rate(world.resource::<DiagnosticsStore>())
+    );
+    println!("Dust data earned:   {}", report.earned(inventory));
+    println!("Dust data spent:    {}", report.spent);
+    for (i, objective) in level.objectives.iter().enumerate() {
+        let mark = if progress.is_met(i) { "x" } else { " " };
+        println!("[{mark}] {}", objective.describe(catalog, format));
+    }
+    match progress.completed_at() {
+        Some(seconds) => println!(
+            "Completed {} after {seconds:.0}s ({:.1} min)",
+            level.id,
+            seconds / 60.0
+        ),
+        None => println!("Did not complete {}", level.id),
+    }
+    if let Some(seconds) = report.lost_at {
+        println!(
+            "Pollution overflowed after {seconds:.0}s ({:.1} min)",
+            seconds / 60.0
+        );
+    }
+}
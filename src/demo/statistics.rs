@@ -0,0 +1,168 @@
+//! Statistics: counters of the current run and of every run so far.
+//!
+//! Both are fed by the same gameplay events. The lifetime totals are stored on
+//! their own like the [achievements](super::achievements), the run's counters
+//! go into its [save](super::save).
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{prelude::*, storage};
+
+use super::{
+    currency::Currency,
+    gameplay::{AttackDustEvent, Dust, DustDestroyed, DustEscaped, EnergySpent},
+    ui::inventory::DustDataChanged,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.insert_resource(Statistics::load());
+
+    app.add_systems(
+        Update,
+        (
+            record_statistics.run_if(in_state(Screen::Gameplay)),
+            tick_time_played
+                .in_set(AppSystems::Update)
+                .run_if(in_state(Screen::Gameplay)),
+        ),
+    );
+    app.add_systems(OnExit(Screen::Gameplay), store_statistics);
+    // also keep the totals when the window is closed mid-run
+    app.add_systems(
+        Last,
+        store_statistics.run_if(in_state(Screen::Gameplay).and(on_event::<AppExit>)),
+    );
+}
+
+const STATISTICS_KEY: &str = "statistics";
+/// Bump when [`Counters`] changes shape.
+const STATISTICS_VERSION: u32 = 2;
+
+/// Everything counted, once for the run and once for every run.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct Counters {
+    pub destroyed: BTreeMap<Dust, u64>,
+    pub escaped: u64,
+    /// Pollution leaked by escaped dust.
+    pub leaked: f64,
+    pub damage_dealt: f64,
+    /// Most dust hit by one lightning chain.
+    pub longest_chain: u32,
+    /// Power spent charging discharge points.
+    pub energy_spent: f64,
+    /// Whole numbers before version 2.
+    pub data_earned: Currency,
+    pub data_spent: Currency,
+    /// Seconds, pauses excluded.
+    pub time_played: f64,
+}
+
+impl Counters {
+    pub fn destroyed_total(&self) -> u64 {
+        self.destroyed.values().sum()
+    }
+}
+
+#[derive(Resource, Debug, Clone, Default)]
+pub struct Statistics {
+    pub run: Counters,
+    pub lifetime: Counters,
+}
+
+/// The stored lifetime totals.
+#[derive(Serialize, Deserialize, Debug)]
+struct StoredStatistics {
+    version: u32,
+    lifetime: Counters,
+}
+
+impl Statistics {
+    /// Read the stored lifetime totals, zero if there are none.
+    fn load() -> Self {
+        let Some(contents) = storage::load(STATISTICS_KEY) else {
+            return Self::default();
+        };
+        match ron::from_str::<StoredStatistics>(&contents) {
+            Ok(stored) if stored.version <= STATISTICS_VERSION => Self {
+                run: Counters::default(),
+                lifetime: stored.lifetime,
+            },
+            Ok(stored) => {
+                warn!(
+                    "Ignoring statistics with unsupported version {}",
+                    stored.version
+                );
+                Self::default()
+            }
+            Err(error) => {
+                warn!("Ignoring unreadable statistics: {error}");
+                Self::default()
+            }
+        }
+    }
+
+    fn store(&self) {
+        let stored = StoredStatistics {
+            version: STATISTICS_VERSION,
+            lifetime: self.lifetime.clone(),
+        };
+        let result = ron::ser::to_string_pretty(&stored, default())
+            .map_err(std::io::Error::other)
+            .and_then(|contents| storage::save(STATISTICS_KEY, &contents));
+        if let Err(error) = result {
+            error!("Could not save statistics: {error}");
+        }
+    }
+
+    /// Count something for the run and the lifetime totals alike.
+    fn record(&mut self, f: impl Fn(&mut Counters)) {
+        f(&mut self.run);
+        f(&mut self.lifetime);
+    }
+}
+
+fn record_statistics(
+    mut statistics: ResMut<Statistics>,
+    mut destroyed: EventReader<DustDestroyed>,
+    mut escaped: EventReader<DustEscaped>,
+    mut attacks: EventReader<AttackDustEvent>,
+    mut energy_spent: EventReader<EnergySpent>,
+    mut data_changed: EventReader<DustDataChanged>,
+) {
+    for DustDestroyed(dust) in destroyed.read() {
+        statistics.record(|counters| *counters.destroyed.entry(*dust).or_default() += 1);
+    }
+    for event in escaped.read() {
+        statistics.record(|counters| {
+            counters.escaped += 1;
+            counters.leaked += event.leak as f64;
+        });
+    }
+    for event in attacks.read() {
+        statistics.record(|counters| {
+            counters.damage_dealt += event.amount as f64;
+            counters.longest_chain = counters.longest_chain.max(event.hop);
+        });
+    }
+    for EnergySpent(energy) in energy_spent.read() {
+        statistics.record(|counters| counters.energy_spent += *energy as f64);
+    }
+    for event in data_changed.read() {
+        statistics.record(|counters| match event {
+            DustDataChanged::Earned(amount) => counters.data_earned += *amount,
+            DustDataChanged::Spent(amount) => counters.data_spent += *amount,
+        });
+    }
+}
+
+fn tick_time_played(mut statistics: ResMut<Statistics>, time: Res<Time>) {
+    let delta = time.delta_secs_f64();
+    statistics.record(|counters| counters.time_played += delta);
+}
+
+fn store_statistics(statistics: Res<Statistics>) {
+    statistics.store();
+}
@@ -0,0 +1,196 @@
+//! Upgrade a single discharge point.
+//!
+//! Left-click a discharge point to select it, the panel then sells upgrades
+//! for that point's own [`AttackerStats`].
+
+use bevy::color::palettes::css::WHITE;
+
+use crate::{
+    demo::{
+        can_draw_gizmos,
+        currency::NumberFormat,
+        gameplay::{Attacker, AttackerStat, AttackerStats, DamageType},
+    },
+    prelude::*,
+};
+
+use super::{
+    inventory::{DustDataChanged, Inventory},
+    purchase::catalog::CostCurve,
+    widget,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<SelectedAttacker>();
+    app.add_systems(OnEnter(Screen::Gameplay), clear_selection);
+    app.add_systems(
+        Update,
+        drop_missing_selection
+            .in_set(AppSystems::Update)
+            .run_if(in_state(Screen::Gameplay)),
+    );
+    app.add_systems(Update, draw_selection.run_if(can_draw_gizmos));
+
+    app.add_observer(select_attacker);
+    app.add_observer(update_attacker_panel);
+}
+
+/// Price of a discharge point's next upgrade, by the number it already has.
+const UPGRADE_COST: CostCurve = CostCurve::exponential(20.0, 1.5);
+
+#[derive(Resource, Debug, Default)]
+pub struct SelectedAttacker(pub Option<Entity>);
+
+/// Rebuild the panel for the current [`SelectedAttacker`].
+#[derive(Event, Debug)]
+pub struct AttackerPanelChanged;
+
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+struct AttackerPanel;
+
+pub fn attacker_panel() -> impl Bundle {
+    (
+        Name::new("Attacker Panel"),
+        AttackerPanel,
+        Node {
+            width: Val::Px(400.0),
+            flex_direction: FlexDirection::Column,
+            row_gap: Val::Px(5.0),
+            ..default()
+        },
+        children![
+            widget::header("Discharge Point"),
+            widget::label("Click a discharge point to upgrade it"),
+        ],
+    )
+}
+
+fn stat_tip(stat: AttackerStat, stats: &AttackerStats) -> String {
+    let next = stats.upgraded(stat);
+    match stat {
+        AttackerStat::Energy => format!("Energy: {:.1}->{:.1}", stats.energy, next.energy),
+        AttackerStat::Interval => {
+            format!("Recharge secs: {:.2}->{:.2}", stats.interval, next.interval)
+        }
+        AttackerStat::Range => format!("Range: {:.0}->{:.0}", stats.range, next.range),
+        AttackerStat::ChainLimit => format!(
+            "Lightning hops: {}->{}",
+            stats.chain_limit, next.chain_limit
+        ),
+    }
+}
+
+fn stat_name(stat: AttackerStat) -> &'static str {
+    match stat {
+        AttackerStat::Energy => "Discharge Energy",
+        AttackerStat::Interval => "Recharge Speed",
+        AttackerStat::Range => "Reach",
+        AttackerStat::ChainLimit => "Chain Length",
+    }
+}
+
+fn update_attacker_panel(
+    _: Trigger<AttackerPanelChanged>,
+    panel: Single<Entity, With<AttackerPanel>>,
+    mut commands: Commands,
+    selected: Res<SelectedAttacker>,
+    attackers: Query<(&Attacker, &AttackerStats)>,
+    format: Res<NumberFormat>,
+) {
+    let selected = selected.0.and_then(|entity| attackers.get(entity).ok());
+    let mut panel = commands.entity(*panel);
+    panel.despawn_related::<Children>();
+    panel.with_children(|parent| {
+        parent.spawn(widget::header("Discharge Point"));
+        let Some((attacker, stats)) = selected else {
+            parent.spawn(widget::label("Click a discharge point to upgrade it"));
+            return;
+        };
+        parent.spawn(widget::label(format!(
+            "{:?}, {} upgrades",
+            attacker.damage_type(),
+            stats.upgrades
+        )));
+        let cost = format.format(UPGRADE_COST.value(stats.upgrades as usize));
+        for stat in AttackerStat::ALL {
+            // only lightning jumps from dust to dust, and only a limited chain
+            // can be lengthened
+            if stat == AttackerStat::ChainLimit
+                && (attacker.damage_type() != DamageType::Lightning || !stats.has_chain_limit())
+            {
+                continue;
+            }
+            parent.spawn(upgrade_row(stat, stats, cost.clone()));
+        }
+    });
+}
+
+fn upgrade_row(stat: AttackerStat, stats: &AttackerStats, cost: String) -> impl Bundle {
+    widget::row(
+        stat_name(stat),
+        stat_tip(stat, stats),
+        cost,
+        move |_: Trigger<Pointer<Click>>,
+              selected: Res<SelectedAttacker>,
+              mut stats: Query<&mut AttackerStats>,
+              mut inventory: ResMut<Inventory>,
+              mut commands: Commands| {
+            let Some(mut stats) = selected.0.and_then(|entity| stats.get_mut(entity).ok()) else {
+                return;
+            };
+            let cost = UPGRADE_COST.value(stats.upgrades as usize);
+            let Some(left) = inventory.dust_data.checked_sub(cost) else {
+                info!("Not enough data to purchase.");
+                return;
+            };
+            inventory.dust_data = left;
+            commands.send_event(DustDataChanged::Spent(cost));
+            *stats = stats.upgraded(stat);
+            commands.trigger(AttackerPanelChanged);
+        },
+    )
+}
+
+fn select_attacker(
+    t: Trigger<Pointer<Click>>,
+    attackers: Query<(), With<Attacker>>,
+    mut selected: ResMut<SelectedAttacker>,
+    mut commands: Commands,
+) {
+    if t.button != PointerButton::Primary || !attackers.contains(t.target()) {
+        return;
+    }
+    selected.0 = Some(t.target());
+    commands.trigger(AttackerPanelChanged);
+}
+
+fn clear_selection(mut selected: ResMut<SelectedAttacker>) {
+    selected.0 = None;
+}
+
+fn drop_missing_selection(
+    mut selected: ResMut<SelectedAttacker>,
+    attackers: Query<(), With<Attacker>>,
+    mut commands: Commands,
+) {
+    if selected.0.is_some_and(|entity| !attackers.contains(entity)) {
+        selected.0 = None;
+        commands.trigger(AttackerPanelChanged);
+    }
+}
+
+fn draw_selection(
+    selected: Res<SelectedAttacker>,
+    attackers: Query<&Transform, With<Attacker>>,
+    mut gizmos: Gizmos,
+) {
+    let Some(transform) = selected.0.and_then(|entity| attackers.get(entity).ok()) else {
+        return;
+    };
+    gizmos.rect_2d(
+        Isometry2d::from_translation(transform.translation.truncate()),
+        Vec2::splat(24.0),
+        WHITE,
+    );
+}
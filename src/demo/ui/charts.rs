@@ -0,0 +1,200 @@
+//! Charts of the economy over the last few minutes, so players can see
+//! whether an upgrade helped.
+
+use std::collections::VecDeque;
+
+use bevy::{
+    color::palettes::{css::GOLD, tailwind::*},
+    diagnostic::DiagnosticsStore,
+    ecs::spawn::SpawnIter,
+};
+
+use crate::{
+    demo::{
+        currency::{Currency, NumberFormat},
+        gameplay::Power,
+    },
+    prelude::*,
+    theme::widget::{ChartBar, ChartTitle, bar_chart, chart_bar_height},
+};
+
+use super::{collect_rate::DUST_COLLECT_RATE_DIAGNOSTIC, inventory::DustDataChanged};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<EconomySampler>();
+    app.init_resource::<EconomyHistory>();
+    app.add_systems(OnEnter(Screen::Gameplay), reset_history);
+
+    app.add_systems(
+        Update,
+        (record_income, sample_economy)
+            .chain()
+            .in_set(AppSystems::Update)
+            .run_if(in_state(Screen::Gameplay)),
+    );
+    app.add_systems(
+        Update,
+        update_charts.run_if(in_state(Screen::Gameplay).and(resource_changed::<EconomyHistory>)),
+    );
+}
+
+/// Seconds between two samples.
+const SAMPLE_SECS: f32 = 2.0;
+/// Samples kept per metric, three minutes' worth.
+const SAMPLES: usize = 90;
+
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+enum Metric {
+    CollectRate,
+    Income,
+    Power,
+}
+
+impl Metric {
+    const ALL: [Self; 3] = [Self::CollectRate, Self::Income, Self::Power];
+
+    fn title(self) -> &'static str {
+        match self {
+            Self::CollectRate => "Collect rate",
+            Self::Income => "Income",
+            Self::Power => "Energy",
+        }
+    }
+
+    fn unit(self) -> &'static str {
+        match self {
+            Self::CollectRate | Self::Income => " dust/s",
+            Self::Power => "",
+        }
+    }
+
+    /// Dust rates in the player's number format, energy as it is.
+    fn format(self, value: f64, format: NumberFormat) -> String {
+        match self {
+            Self::CollectRate | Self::Income => format.format(Currency::from_f64(value)),
+            Self::Power => format!("{value:.1}"),
+        }
+    }
+
+    fn color(self) -> Color {
+        match self {
+            Self::CollectRate => GOLD.into(),
+            Self::Income => EMERALD_400.into(),
+            Self::Power => SKY_400.into(),
+        }
+    }
+}
+
+/// Collects the income between two samples.
+#[derive(Resource, Debug)]
+struct EconomySampler {
+    timer: Timer,
+    income: Currency,
+}
+
+impl Default for EconomySampler {
+    fn default() -> Self {
+        Self {
+            timer: Timer::from_seconds(SAMPLE_SECS, TimerMode::Repeating),
+            income: Currency::ZERO,
+        }
+    }
+}
+
+/// The latest samples of each [`Metric`], oldest first.
+#[derive(Resource, Debug, Default)]
+struct EconomyHistory {
+    series: [VecDeque<f64>; Metric::ALL.len()],
+}
+
+impl EconomyHistory {
+    fn series(&self, metric: Metric) -> &VecDeque<f64> {
+        &self.series[metric as usize]
+    }
+
+    fn push(&mut self, metric: Metric, value: f64) {
+        let series = &mut self.series[metric as usize];
+        if series.len() == SAMPLES {
+            series.pop_front();
+        }
+        series.push_back(value);
+    }
+}
+
+/// Every run starts with empty charts.
+fn reset_history(mut history: ResMut<EconomyHistory>, mut sampler: ResMut<EconomySampler>) {
+    *history = EconomyHistory::default();
+    *sampler = EconomySampler::default();
+}
+
+fn record_income(mut events: EventReader<DustDataChanged>, mut sampler: ResMut<EconomySampler>) {
+    for event in events.read() {
+        if let DustDataChanged::Earned(amount) = event {
+            sampler.income += *amount;
+        }
+    }
+}
+
+fn sample_economy(
+    mut sampler: ResMut<EconomySampler>,
+    mut history: ResMut<EconomyHistory>,
+    diagnostics: Res<DiagnosticsStore>,
+    power: Res<Power>,
+    time: Res<Time>,
+) {
+    if !sampler.timer.tick(time.delta()).just_finished() {
+        return;
+    }
+    let collect_rate = diagnostics
+        .get(&DUST_COLLECT_RATE_DIAGNOSTIC)
+        .and_then(|rate| rate.smoothed())
+        .unwrap_or_default();
+    history.push(Metric::CollectRate, collect_rate);
+    history.push(Metric::Income, sampler.income.to_f64() / SAMPLE_SECS as f64);
+    history.push(Metric::Power, power.current() as f64);
+    sampler.income = Currency::ZERO;
+}
+
+pub fn economy_charts() -> impl Bundle {
+    (
+        Name::new("Economy Charts"),
+        Node {
+            flex_direction: FlexDirection::Column,
+            row_gap: Val::Px(5.0),
+            ..default()
+        },
+        StateScoped(Screen::Gameplay),
+        Pickable::IGNORE,
+        Children::spawn(SpawnIter(Metric::ALL.into_iter().map(|metric| {
+            bar_chart(metric.title(), metric.color(), SAMPLES, metric)
+        }))),
+    )
+}
+
+fn update_charts(
+    history: Res<EconomyHistory>,
+    mut bars: Query<(&mut Node, &ChartBar, &Metric)>,
+    mut titles: Query<(&mut Text, &Metric), With<ChartTitle>>,
+    format: Res<NumberFormat>,
+) {
+    let plots = Metric::ALL.map(|metric| {
+        let values = history.series(metric).iter().copied().collect::<Vec<_>>();
+        let max = values.iter().copied().fold(0.0, f64::max);
+        (values, max)
+    });
+    for (mut node, bar, metric) in &mut bars {
+        let (values, max) = &plots[*metric as usize];
+        node.height = chart_bar_height(values, SAMPLES, *bar, *max);
+    }
+    for (mut text, metric) in &mut titles {
+        let (values, max) = &plots[*metric as usize];
+        let latest = values.last().copied().unwrap_or_default();
+        text.0 = format!(
+            "{} {}{} (max {})",
+            metric.title(),
+            metric.format(latest, *format),
+            metric.unit(),
+            metric.format(*max, *format)
+        );
+    }
+}
@@ -1,50 +1,36 @@
 use bevy::{
-    color::palettes::css::GOLD,
+    color::palettes::{css::GOLD, tailwind::STONE_400},
     diagnostic::{Diagnostic, DiagnosticPath, DiagnosticsStore, RegisterDiagnostic},
 };
 
-use crate::{
-    menus::{COMPLETE_COLLECTION_RATE, CompleteTheGame},
-    prelude::*,
-    theme::palette::PURCHASE_ROW_BACKGROUND,
-};
+use crate::{prelude::*, theme::palette::PURCHASE_ROW_BACKGROUND};
 
 pub(super) fn plugin(app: &mut App) {
     app.register_diagnostic(
         Diagnostic::new(DUST_COLLECT_RATE_DIAGNOSTIC).with_smoothing_factor(5.0),
     );
+    app.register_diagnostic(Diagnostic::new(DUST_LEAK_RATE_DIAGNOSTIC).with_smoothing_factor(5.0));
 
     app.add_systems(Update, update_collect_rate);
 
-    app.add_systems(
-        Update,
-        send_complete_event.run_if(in_state(Screen::Gameplay)),
-    );
+    app.add_systems(OnEnter(Screen::Gameplay), reset_rates);
 }
 
 pub const DUST_COLLECT_RATE_DIAGNOSTIC: DiagnosticPath =
     DiagnosticPath::const_new("dust_collect_rate");
+/// Pollution leaked per second by dust escaping at the bottom.
+pub const DUST_LEAK_RATE_DIAGNOSTIC: DiagnosticPath = DiagnosticPath::const_new("dust_leak_rate");
 
-pub fn send_complete_event(
-    mut commands: Commands,
-    mut marker: Local<bool>,
-    diagnostics: Res<DiagnosticsStore>,
-) {
-    if *marker {
-        return; // Already sent the event
-    }
-    if let Some(rate) = diagnostics
-        .get(&DUST_COLLECT_RATE_DIAGNOSTIC)
-        .and_then(|rate| rate.smoothed())
-    {
-        if rate >= COMPLETE_COLLECTION_RATE {
-            *marker = true; // Set marker to true to prevent multiple sends
-            commands.send_event(CompleteTheGame);
+/// Start every run without the rates of the previous one.
+fn reset_rates(mut diagnostics: ResMut<DiagnosticsStore>) {
+    for path in [DUST_COLLECT_RATE_DIAGNOSTIC, DUST_LEAK_RATE_DIAGNOSTIC] {
+        if let Some(diagnostic) = diagnostics.get_mut(&path) {
+            diagnostic.clear_history();
         }
     }
 }
 
-pub fn goal_ui() -> impl Bundle {
+pub fn collect_rate_ui() -> impl Bundle {
     (
         Name::new("Collect Rate UI"),
         Node {
@@ -60,41 +46,44 @@ pub fn goal_ui() -> impl Bundle {
         BackgroundColor(PURCHASE_ROW_BACKGROUND),
         StateScoped(Screen::Gameplay),
         children![
-            (
-                Text::new("Goal: Increase Dust Collection Rate"),
-                TextColor::from(Color::BLACK),
-                TextFont::from_font_size(20.0)
-            ),
-            row(),
+            row("Collect Rate: ", RateTextSpan::Collect, GOLD),
+            row("Leak Rate: ", RateTextSpan::Leak, STONE_400),
         ],
     )
 }
 
-#[derive(Component, Reflect, Debug)]
+#[derive(Component, Reflect, Debug, Clone, Copy)]
 #[reflect(Component)]
-struct CollectRateTextSpan;
+enum RateTextSpan {
+    Collect,
+    Leak,
+}
 
-fn row() -> impl Bundle {
+impl RateTextSpan {
+    fn diagnostic(self) -> DiagnosticPath {
+        match self {
+            Self::Collect => DUST_COLLECT_RATE_DIAGNOSTIC,
+            Self::Leak => DUST_LEAK_RATE_DIAGNOSTIC,
+        }
+    }
+}
+
+fn row(label: &'static str, marker: RateTextSpan, color: Srgba) -> impl Bundle {
     (
-        Name::new("Collect Rate Row"),
-        Node {
-            // flex_direction: FlexDirection::Row,
-            // justify_content: JustifyContent::Center,
-            // align_items: AlignItems::Center,
-            ..default()
-        },
-        Text::new("Collect Rate: "),
+        Name::new("Rate Row"),
+        Node::default(),
+        Text::new(label),
         children![
             (
                 TextSpan::new("0.0"),
-                CollectRateTextSpan,
+                marker,
                 TextFont::from_font_size(20.0),
-                TextColor::from(GOLD),
+                TextColor::from(color),
             ),
             (
                 TextSpan::new(" dust/s"),
                 TextFont::from_font_size(20.0),
-                TextColor::from(GOLD),
+                TextColor::from(color),
             )
         ],
     )
@@ -102,12 +91,14 @@ fn row() -> impl Bundle {
 
 fn update_collect_rate(
     diagnostics: Res<DiagnosticsStore>,
-    mut textspan: Single<&mut TextSpan, With<CollectRateTextSpan>>,
+    mut textspans: Query<(&mut TextSpan, &RateTextSpan)>,
 ) {
-    if let Some(rate) = diagnostics
-        .get(&DUST_COLLECT_RATE_DIAGNOSTIC)
-        .and_then(|rate| rate.smoothed())
-    {
-        textspan.0 = format!("{:.1}", rate);
+    for (mut textspan, marker) in &mut textspans {
+        if let Some(rate) = diagnostics
+            .get(&marker.diagnostic())
+            .and_then(|rate| rate.smoothed())
+        {
+            textspan.0 = format!("{:.1}", rate);
+        }
     }
 }
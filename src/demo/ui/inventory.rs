@@ -1,6 +1,10 @@
 use bevy::ecs::{relationship::RelatedSpawner, spawn::SpawnWith};
 
 use crate::{
+    demo::{
+        currency::{Currency, NumberFormat},
+        gameplay::PollutionMeter,
+    },
     prelude::*,
     theme::widget::{header, label},
 };
@@ -8,28 +12,54 @@ use crate::{
 pub(super) fn plugin(app: &mut App) {
     // app.init_resource::<Inventory>();
     app.register_type::<Inventory>();
-    app.insert_resource(Inventory {
-        dust_data: 0,
-        timer: Timer::from_seconds(0.5, TimerMode::Repeating),
-    });
+    app.init_resource::<Inventory>();
+    app.add_event::<DustDataChanged>();
 
     app.add_systems(
         Update,
         (
-            update_inventory_ui.run_if(resource_changed::<Inventory>),
+            update_inventory_ui
+                .run_if(resource_changed::<Inventory>.or(resource_changed::<NumberFormat>)),
             add_dust_data_constantly.run_if(in_state(Screen::Gameplay)),
         )
             .in_set(AppSystems::Update),
     );
 }
 
-#[derive(Resource, Reflect, Debug, Default)]
+#[derive(Resource, Reflect, Debug)]
 #[reflect(Resource)]
 pub struct Inventory {
-    pub dust_data: u32,
+    pub dust_data: Currency,
+    /// Dust data collected from dust this run, spent or not.
+    pub collected: Currency,
     timer: Timer,
 }
 
+impl Inventory {
+    /// Dust data earned per second without collecting any dust, before the
+    /// [pollution](PollutionMeter::income_factor) penalty.
+    pub fn passive_rate(&self) -> f64 {
+        1.0 / self.timer.duration().as_secs_f64()
+    }
+}
+
+impl Default for Inventory {
+    fn default() -> Self {
+        Self {
+            dust_data: Currency::ZERO,
+            collected: Currency::ZERO,
+            timer: Timer::from_seconds(0.5, TimerMode::Repeating),
+        }
+    }
+}
+
+/// Dust data earned or spent, for the [statistics](crate::demo::statistics).
+#[derive(Event, Debug, Clone, Copy)]
+pub enum DustDataChanged {
+    Earned(Currency),
+    Spent(Currency),
+}
+
 #[derive(Component, Reflect, Debug)]
 #[reflect(Component)]
 #[allow(non_camel_case_types)]
@@ -53,20 +83,30 @@ pub fn inventory_ui() -> impl Bundle {
     )
 }
 
-fn add_dust_data_constantly(mut inventory: ResMut<Inventory>, time: Res<Time>) {
-    if inventory.timer.tick(time.delta()).just_finished() {
-        inventory.dust_data += 1; // Increment dust data every second
+fn add_dust_data_constantly(
+    mut inventory: ResMut<Inventory>,
+    mut data_changed: EventWriter<DustDataChanged>,
+    meter: Res<PollutionMeter>,
+    time: Res<Time>,
+) {
+    // pollution slows the data center down
+    let delta = time.delta().mul_f32(meter.income_factor());
+    if inventory.timer.tick(delta).just_finished() {
+        let earned = Currency::from(1);
+        inventory.dust_data += earned;
+        data_changed.write(DustDataChanged::Earned(earned));
     }
 }
 
 fn update_inventory_ui(
     mut textspans: Query<(&mut TextSpan, &InventoryFields)>,
     inventory: Res<Inventory>,
+    format: Res<NumberFormat>,
 ) -> Result {
     for (mut textspan, field) in textspans.iter_mut() {
         match field {
             InventoryFields::dust_data => {
-                textspan.0 = inventory.dust_data.to_string();
+                textspan.0 = format.format(inventory.dust_data);
             }
         }
     }
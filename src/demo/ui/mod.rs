@@ -1,10 +1,18 @@
 use crate::prelude::*;
 
+pub mod attacker_panel;
+pub mod charts;
 pub mod collect_rate;
 pub mod inventory;
 pub mod purchase;
 mod widget;
 
 pub(super) fn plugin(app: &mut App) {
-    app.add_plugins((inventory::plugin, purchase::plugin, collect_rate::plugin));
+    app.add_plugins((
+        inventory::plugin,
+        purchase::plugin,
+        collect_rate::plugin,
+        attacker_panel::plugin,
+        charts::plugin,
+    ));
 }
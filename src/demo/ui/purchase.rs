@@ -1,192 +1,569 @@
+use std::collections::{HashMap, HashSet};
+
 use bevy::ecs::relationship::RelatedSpawner;
 use bevy::ecs::spawn::SpawnWith;
 
-use super::widget;
+use super::widget::{self, RowButton, RowCost, RowTip};
 
-use crate::demo::ChangePlayerStats;
-use crate::demo::gameplay::SetDustSpawnStats;
-use crate::demo::gameplay::SetPowerStats;
-use crate::demo::gameplay::SpawnAttacker;
+use crate::demo::{
+    currency::{Currency, NumberFormat},
+    level::{CurrentLevel, catalog::LevelDef},
+    prestige::{MetaBonuses, MetaProgress},
+    ui::inventory::{DustDataChanged, Inventory},
+};
 use crate::prelude::*;
+use crate::theme::{interaction::InteractionDisabled, palette::LABEL_TEXT};
 
+pub mod auto_buy;
+pub mod catalog;
+mod estimate;
 mod types;
-use types::*;
+use auto_buy::auto_buy_toggle;
+use catalog::*;
+use estimate::ESTIMATE_HINT;
 
 pub(super) fn plugin(app: &mut App) {
+    app.init_asset::<UpgradeCatalog>();
+    app.init_asset_loader::<UpgradeCatalogLoader>();
+    app.configure_loading_state(
+        LoadingStateConfig::new(AssetsState::Loading).load_collection::<UpgradeAssets>(),
+    );
+    app.add_systems(OnEnter(AssetsState::Done), insert_upgrade_catalog);
+    app.add_systems(
+        Update,
+        reload_upgrade_catalog.run_if(on_event::<AssetEvent<UpgradeCatalog>>),
+    );
+
     app.init_resource::<ShopState>();
+    app.init_resource::<BuyAmount>();
+    app.add_event::<UpgradePurchased>();
     app.add_observer(update_purchase_ui);
+    app.add_systems(
+        Update,
+        (
+            update_rows.run_if(
+                resource_changed::<ShopState>
+                    .or(resource_changed::<Inventory>)
+                    .or(resource_changed::<BuyAmount>)
+                    .or(resource_changed::<NumberFormat>)
+                    .or(any_match_filter::<Added<UpgradeRow>>),
+            ),
+            update_buy_amount_label.run_if(
+                resource_changed::<BuyAmount>.or(any_match_filter::<Added<BuyAmountButton>>),
+            ),
+        )
+            .in_set(AppSystems::Update)
+            .run_if(any_with_component::<UpgradeRow>),
+    );
+
+    app.add_plugins((estimate::plugin, auto_buy::plugin));
+}
+
+/// How many levels one click in the Research Lab buys.
+#[derive(Resource, Reflect, Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[reflect(Resource)]
+pub enum BuyAmount {
+    #[default]
+    One,
+    Ten,
+    /// As many as the dust data pays for, up to [`MAX_BULK_LEVELS`].
+    Max,
+}
+
+/// Most levels [`BuyAmount::Max`] buys at once, so a late-game budget doesn't
+/// walk the costs for ages.
+const MAX_BULK_LEVELS: usize = 1000;
+
+impl BuyAmount {
+    fn next(self) -> Self {
+        match self {
+            Self::One => Self::Ten,
+            Self::Ten => Self::Max,
+            Self::Max => Self::One,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::One => "Buy x1",
+            Self::Ten => "Buy x10",
+            Self::Max => "Buy max",
+        }
+    }
+}
+
+/// Levels one purchase buys and their total price.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Quote {
+    pub levels: usize,
+    pub cost: Currency,
+}
+
+/// The parts of the Research Lab row of an upgrade that change, by
+/// [`UpgradeDef::id`].
+#[derive(Component, Debug, Clone)]
+struct UpgradeRow(String);
+
+#[derive(Component, Debug)]
+struct BuyAmountButton;
+
+/// Sent for every Research Lab level bought.
+#[derive(Event, Debug, Clone)]
+pub struct UpgradePurchased {
+    /// The upgrade has no levels left to buy.
+    pub maxed: bool,
 }
 
 #[derive(Component, Reflect, Debug)]
 #[reflect(Component)]
 struct PurchaseUI;
 
+/// Rebuild the Research Lab panel, when the catalog changes which rows it has.
+/// Purchases only update the rows in place.
 #[derive(Event, Debug)]
-struct PurchaseUIChanged;
+pub struct PurchaseUIChanged;
 
 fn update_purchase_ui(
     _: Trigger<PurchaseUIChanged>,
     ui: Single<(Entity, &ChildOf), With<PurchaseUI>>,
     mut commands: Commands,
     shop_state: Res<ShopState>,
+    catalog: Res<UpgradeCatalog>,
+    level: Res<CurrentLevel>,
 ) {
     let (ui, parent) = *ui;
     commands.entity(ui).despawn();
-    commands.entity(parent.0).with_child(shop_state.render());
+    commands
+        .entity(parent.0)
+        .with_child(shop_state.render(&catalog, &level));
 }
 
-macro_rules! define_upgrade {
-    (
-        $const_name:ident,       // 常量名
-        $item_name:expr,         // item_name 字段值
-        $tips:expr,         // tips 字段值
-        $effect_type:ident::new($effect_init:expr, $effect_ratio:expr),      // effects
-        $cost_type:ident::new($cost_init:expr, $cost_ratio:expr), // costs
-        $event_type:ty,       // 输出event 的类型
-        $effect_fn:expr        // 输出effect event 的函数
-    ) => {
-        #[allow(non_camel_case_types)]
-        struct $const_name {
-            item_name: &'static str,
-            tips: &'static str,
-            effects: $effect_type,
-            costs: $cost_type,
+/// Keep the tips, costs, affordability and visibility of the rows up to date.
+fn update_rows(
+    mut commands: Commands,
+    mut tips: Query<(&mut Text, &UpgradeRow), (With<RowTip>, Without<RowCost>)>,
+    mut costs: Query<(&mut Text, &UpgradeRow), (With<RowCost>, Without<RowTip>)>,
+    buttons: Query<(Entity, &UpgradeRow, Has<InteractionDisabled>), With<RowButton>>,
+    mut nodes: Query<(&mut Node, &UpgradeRow, &TechTreeNode), Without<TierHeader>>,
+    mut headers: Query<(&mut Node, &TierHeader)>,
+    shop_state: Res<ShopState>,
+    catalog: Res<UpgradeCatalog>,
+    inventory: Res<Inventory>,
+    amount: Res<BuyAmount>,
+    format: Res<NumberFormat>,
+) {
+    let collected = inventory.collected;
+    for (mut text, row) in &mut tips {
+        let Some(upgrade) = catalog.get(&row.0) else {
+            continue;
+        };
+        text.set_if_neq(Text(shop_state.tip(upgrade, &catalog, collected, *format)));
+    }
+    for (mut text, row) in &mut costs {
+        let Some(upgrade) = catalog.get(&row.0) else {
+            continue;
+        };
+        let quote = shop_state.quote(upgrade, *amount, inventory.dust_data);
+        let line = match quote.levels {
+            0 => "Cost: -".to_string(),
+            1 => format!("Cost: {}", format.format(quote.cost)),
+            levels => format!("x{levels}: {}", format.format(quote.cost)),
+        };
+        text.set_if_neq(Text(line));
+    }
+    for (button, row, disabled) in &buttons {
+        let Some(upgrade) = catalog.get(&row.0) else {
+            continue;
+        };
+        let quote = shop_state.quote(upgrade, *amount, inventory.dust_data);
+        let affordable = shop_state.is_unlocked(upgrade, collected)
+            && quote.levels > 0
+            && quote.cost <= inventory.dust_data;
+        if affordable && disabled {
+            commands.entity(button).remove::<InteractionDisabled>();
+        } else if !affordable && !disabled {
+            commands.entity(button).insert(InteractionDisabled);
+        }
+    }
+    let mut shown_tiers = HashSet::new();
+    for (mut node, row, tree_node) in &mut nodes {
+        let Some(upgrade) = catalog.get(&row.0) else {
+            continue;
+        };
+        let shown = !upgrade.hidden || shop_state.is_unlocked(upgrade, collected);
+        if shown {
+            shown_tiers.insert(tree_node.tier);
+        }
+        set_shown(&mut node, shown);
+    }
+    for (mut node, header) in &mut headers {
+        set_shown(&mut node, shown_tiers.contains(&header.0));
+    }
+}
+
+fn set_shown(node: &mut Node, shown: bool) {
+    let display = if shown { Display::Flex } else { Display::None };
+    if node.display != display {
+        node.display = display;
+    }
+}
+
+fn update_buy_amount_label(
+    amount: Res<BuyAmount>,
+    buttons: Query<&Children, With<BuyAmountButton>>,
+    mut texts: Query<&mut Text>,
+) {
+    for children in &buttons {
+        let mut texts = texts.iter_many_mut(children);
+        while let Some(mut text) = texts.fetch_next() {
+            text.set_if_neq(Text(amount.label().to_string()));
         }
+    }
+}
+
+fn insert_upgrade_catalog(
+    mut commands: Commands,
+    upgrade_assets: Res<UpgradeAssets>,
+    catalogs: Res<Assets<UpgradeCatalog>>,
+) {
+    if let Some(catalog) = catalogs.get(&upgrade_assets.catalog) {
+        info!("Loaded {} upgrades", catalog.upgrades.len());
+        commands.insert_resource(catalog.clone());
+    }
+}
 
-        const $const_name: $const_name = $const_name {
-            item_name: $item_name,
-            tips: $tips,
-            effects: $effect_type::new($effect_init, $effect_ratio),
-            costs: $cost_type::new($cost_init, $cost_ratio),
+/// Mirror a hot-reloaded catalog into the [`UpgradeCatalog`] resource. The
+/// bought levels are re-applied so tuned curves take effect immediately.
+fn reload_upgrade_catalog(
+    mut commands: Commands,
+    mut events: EventReader<AssetEvent<UpgradeCatalog>>,
+    catalogs: Res<Assets<UpgradeCatalog>>,
+    shop_state: Res<ShopState>,
+    meta: Res<MetaProgress>,
+) {
+    for event in events.read() {
+        let AssetEvent::Modified { id } = event else {
+            continue;
         };
+        let Some(catalog) = catalogs.get(*id) else {
+            continue;
+        };
+        info!("Reloaded {} upgrades", catalog.upgrades.len());
+        shop_state.reapply(catalog, &meta.bonuses(), &mut commands);
+        commands.insert_resource(catalog.clone());
+        commands.trigger(PurchaseUIChanged);
+    }
+}
 
-        impl Upgrades for $const_name {
-            type Effect = $event_type;
+/// Bought levels of every upgrade, keyed by [`UpgradeDef::id`].
+#[derive(Resource, Reflect, Debug, Default, Clone)]
+#[reflect(Resource)]
+pub struct ShopState {
+    levels: HashMap<String, usize>,
+}
 
-            fn name(&self) -> &'static str {
-                self.item_name
-            }
-            fn tips(&self) -> &'static str {
-                self.tips
+impl ShopState {
+    pub fn levels(&self) -> &HashMap<String, usize> {
+        &self.levels
+    }
+
+    /// Replace every level, e.g. when restoring a save. No effects are applied.
+    pub fn set_levels(&mut self, levels: HashMap<String, usize>) {
+        self.levels = levels;
+    }
+
+    pub fn level(&self, id: &str) -> usize {
+        self.levels.get(id).copied().unwrap_or_default()
+    }
+
+    /// Cost of the next level of an upgrade.
+    pub fn cost(&self, upgrade: &UpgradeDef) -> Currency {
+        upgrade.cost.value(self.level(&upgrade.id))
+    }
+
+    /// Whether the highest level of an upgrade was bought.
+    pub fn is_maxed(&self, upgrade: &UpgradeDef) -> bool {
+        upgrade
+            .max_level()
+            .is_some_and(|max| self.level(&upgrade.id) >= max)
+    }
+
+    /// Whether every prerequisite of an upgrade is met, with `collected` dust
+    /// data collected this run.
+    pub fn is_unlocked(&self, upgrade: &UpgradeDef, collected: Currency) -> bool {
+        upgrade
+            .requires
+            .iter()
+            .all(|prerequisite| self.is_met(prerequisite, collected))
+    }
+
+    /// Whether the next level of an upgrade can be bought, money aside.
+    pub fn is_available(&self, upgrade: &UpgradeDef, collected: Currency) -> bool {
+        self.is_unlocked(upgrade, collected) && !self.is_maxed(upgrade)
+    }
+
+    fn is_met(&self, prerequisite: &Prerequisite, collected: Currency) -> bool {
+        match prerequisite {
+            Prerequisite::Upgrade { id, level } => self.level(id) >= *level,
+            Prerequisite::Collected(amount) => collected >= *amount,
+        }
+    }
+
+    /// The levels `amount` buys next and their total price. [`BuyAmount::Max`]
+    /// buys as many as `budget` pays for, but is quoted at least one level.
+    pub fn quote(&self, upgrade: &UpgradeDef, amount: BuyAmount, budget: Currency) -> Quote {
+        let level = self.level(&upgrade.id);
+        let left = upgrade
+            .max_level()
+            .map_or(usize::MAX, |max| max.saturating_sub(level));
+        let levels = match amount {
+            BuyAmount::One => 1,
+            BuyAmount::Ten => 10,
+            BuyAmount::Max => {
+                let mut total = Currency::ZERO;
+                let affordable = upgrade
+                    .cost
+                    .costs(level)
+                    .take(left.min(MAX_BULK_LEVELS))
+                    .take_while(|cost| {
+                        total += *cost;
+                        total <= budget
+                    })
+                    .count();
+                affordable.max(1)
             }
+        }
+        .min(left);
+        Quote {
+            levels,
+            cost: upgrade.cost.total(level, levels),
+        }
+    }
+
+    /// Buy the next levels of an upgrade, all or none. Returns whether they
+    /// were unlocked and affordable.
+    pub fn purchase(
+        &mut self,
+        upgrade: &UpgradeDef,
+        amount: BuyAmount,
+        inventory: &mut Inventory,
+        bonuses: &MetaBonuses,
+        commands: &mut Commands,
+    ) -> bool {
+        if !self.is_unlocked(upgrade, inventory.collected) {
+            return false;
+        }
+        let quote = self.quote(upgrade, amount, inventory.dust_data);
+        if quote.levels == 0 {
+            return false;
+        }
+        let Some(left) = inventory.dust_data.checked_sub(quote.cost) else {
+            return false;
+        };
+        inventory.dust_data = left;
+        commands.send_event(DustDataChanged::Spent(quote.cost));
+        let start = self.level(&upgrade.id);
+        let end = start + quote.levels;
+        self.levels.insert(upgrade.id.clone(), end);
+        // every level takes effect, e.g. each adds a discharge point
+        for level in start + 1..=end {
+            let value = bonuses.modify(upgrade.target, upgrade.effect.value(level));
+            upgrade.target.apply(value, commands);
+            commands.send_event(UpgradePurchased {
+                maxed: upgrade.max_level().is_some_and(|max| level >= max),
+            });
+        }
+        true
+    }
 
-            fn get_current_upgrade(&self, level: usize) -> Option<(Self::Effect, (f32, f32), u32)> {
-                let mut combine_iter = self.effects.clone().zip(self.costs.clone());
-                let (cur_effect, cost) = combine_iter.nth(level)?;
-                let (effect, _) = combine_iter.next()?;
-                Some(($effect_fn(effect), (cur_effect, effect), cost))
+    /// Apply the current level of every upgrade again.
+    pub fn reapply(
+        &self,
+        catalog: &UpgradeCatalog,
+        bonuses: &MetaBonuses,
+        commands: &mut Commands,
+    ) {
+        for upgrade in &catalog.upgrades {
+            let level = self.level(&upgrade.id);
+            if level > 0 && upgrade.target.is_idempotent() {
+                let value = bonuses.modify(upgrade.target, upgrade.effect.value(level));
+                upgrade.target.apply(value, commands);
             }
         }
-    };
-}
-
-macro_rules! shop_state {
-    ($($field:ident -> $target:ident)+) => {
-        #[derive(Resource, Reflect, Debug, Default, Clone)]
-        #[reflect(Resource)]
-        #[allow(non_snake_case)]
-        pub struct ShopState {
-            $(
-                $field: usize,
-            )+
+    }
+
+    /// The stat change the next level of an upgrade makes, or what is missing
+    /// to unlock it.
+    fn tip(
+        &self,
+        upgrade: &UpgradeDef,
+        catalog: &UpgradeCatalog,
+        collected: Currency,
+        format: NumberFormat,
+    ) -> String {
+        let missing = upgrade
+            .requires
+            .iter()
+            .filter(|prerequisite| !self.is_met(prerequisite, collected))
+            .map(|prerequisite| prerequisite.describe(catalog, format))
+            .collect::<Vec<_>>();
+        if !missing.is_empty() {
+            return format!("Needs {}", missing.join(", "));
         }
-        #[derive(Clone, Copy)]
-        enum UpgradeItems {
-            $(
-                $field,
-            )+
+        if self.is_maxed(upgrade) {
+            return format!("{}: done", upgrade.tips);
         }
+        let level = self.level(&upgrade.id);
+        format!(
+            "{}: {:.1}->{:.1}",
+            upgrade.tips,
+            upgrade.effect.value(level),
+            upgrade.effect.value(level + 1)
+        )
+    }
 
-        impl ShopState {
-            pub fn render(&self) -> impl Bundle {
-                use UpgradeItems::*;
-                let levels = self.clone();
+    /// The Research Lab panel, with the upgrades sold in `level` laid out as a
+    /// tech tree: one section per [tier](UpgradeCatalog::tiers), prerequisites
+    /// first. Its rows stay and update as upgrades are bought.
+    pub fn render(&self, catalog: &UpgradeCatalog, level: &LevelDef) -> impl Bundle {
+        let tiers = catalog.tiers().unwrap_or_default();
+        let mut rows = catalog
+            .upgrades
+            .iter()
+            .filter(|upgrade| level.allows(&upgrade.id))
+            .map(|upgrade| {
                 (
-                    Name::new("Purchase UI"),
-                    PurchaseUI,
-                    Node {
-                        width: Val::Px(400.0),
-                        flex_direction: FlexDirection::Column,
-                        row_gap: Val::Px(5.0),
-                        ..default()
-                    },
-                    Children::spawn(SpawnWith(move |parent: &mut RelatedSpawner<_>| {
-                        parent.spawn(widget::header("Research Lab"));
-                        $(
-                            if let Some(row) = $target.row(levels.$field, $field) {
-                                parent.spawn(row);
-                            }
-                        )+
-                    })),
+                    tiers.get(upgrade.id.as_str()).copied().unwrap_or_default(),
+                    upgrade.id.clone(),
+                    upgrade.name.clone(),
+                    upgrade.target.explain(),
                 )
-            }
-
-            fn update_by_event(&mut self, item: UpgradeItems) {
-                use UpgradeItems::*;
-                match item {
-                    $(
-                        $field => self.$field += 1,
-                    )+
+            })
+            .collect::<Vec<_>>();
+        // stable, so a tier keeps the catalog order
+        rows.sort_by_key(|(tier, ..)| *tier);
+        (
+            Name::new("Purchase UI"),
+            PurchaseUI,
+            Node {
+                width: Val::Px(400.0),
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(5.0),
+                ..default()
+            },
+            Children::spawn(SpawnWith(move |parent: &mut RelatedSpawner<_>| {
+                parent.spawn(widget::header("Research Lab"));
+                parent.spawn(buy_amount_button());
+                let mut previous_tier = None;
+                for (tier, id, name, explanation) in rows {
+                    if previous_tier != Some(tier) {
+                        parent.spawn(tier_header(tier));
+                        previous_tier = Some(tier);
+                    }
+                    parent
+                        .spawn(tech_tree_node(tier, id.clone()))
+                        .with_children(|node| {
+                            node.spawn(upgrade_row(id.clone(), name, explanation))
+                                .with_child(auto_buy_toggle(id));
+                        });
                 }
+            })),
+        )
+    }
+}
+
+/// Indents the row of an upgrade by its tier, hidden while the upgrade is.
+#[derive(Component, Debug)]
+struct TechTreeNode {
+    tier: usize,
+}
+
+#[derive(Component, Debug)]
+struct TierHeader(usize);
+
+fn tier_header(tier: usize) -> impl Bundle {
+    (
+        Name::new("Tier Header"),
+        TierHeader(tier),
+        Text(format!("Tier {}", tier + 1)),
+        TextFont::from_font_size(16.0),
+        TextColor(LABEL_TEXT),
+    )
+}
+
+fn tech_tree_node(tier: usize, id: String) -> impl Bundle {
+    (
+        Name::new("Tech Tree Node"),
+        TechTreeNode { tier },
+        UpgradeRow(id),
+        Node {
+            width: Val::Percent(100.0),
+            padding: UiRect::left(Val::Px(TIER_INDENT * tier as f32)),
+            ..default()
+        },
+    )
+}
+
+/// Indentation per tier of the tech tree, in pixels.
+const TIER_INDENT: f32 = 12.0;
+
+fn upgrade_row(id: String, name: String, explanation: &str) -> impl Bundle {
+    let tooltip = widget::tooltip(format!("{explanation}\n\n{ESTIMATE_HINT}"));
+    // the tip and cost depend on the run, `update_rows` fills them in
+    let row = widget::row_with_note(
+        name,
+        "",
+        "",
+        UpgradeRow(id.clone()),
+        move |_t: Trigger<Pointer<Click>>,
+              mut inventory: ResMut<Inventory>,
+              mut commands: Commands,
+              mut shop_state: ResMut<ShopState>,
+              catalog: Res<UpgradeCatalog>,
+              meta: Res<MetaProgress>,
+              amount: Res<BuyAmount>| {
+            let Some(upgrade) = catalog.get(&id) else {
+                return;
+            };
+            if shop_state.purchase(
+                upgrade,
+                *amount,
+                &mut inventory,
+                &meta.bonuses(),
+                &mut commands,
+            ) {
+                return;
             }
-        }
-    };
-}
-
-define_upgrade!(
-    STATIC_DISCHARGE_POWER,
-    "Static Discharge Power",
-    "Max release",
-    MultiplicativeEffect::new(5.0, 1.1),
-    ExpCosts::new(10.0, 1.2),
-    ChangePlayerStats,
-    ChangePlayerStats::SetAttackEnergy
-);
-
-define_upgrade!(
-    NEW_DISCHARGE_POINT,
-    "Discharge Points",
-    "Number of points",
-    AdditiveEffect::new(1.0, 1.0),
-    ExpCosts::new(25.0, 1.4),
-    SpawnAttacker,
-    |_| SpawnAttacker // 因为没有 event 参数
-);
-
-define_upgrade!(
-    ENERGY_RECOVERY,
-    "Energy Recovery",
-    "Recovery per sec",
-    MultiplicativeEffect::new(10.0, 1.5),
-    ExpCosts::new(15.0, 1.6),
-    SetPowerStats,
-    SetPowerStats::RegenSpeed
-);
-
-define_upgrade!(
-    ENERGY_CAP,
-    "Energy Capacity",
-    "Maximum energy",
-    MultiplicativeEffect::new(8.0, 1.1),
-    ExpCosts::new(40.0, 1.2),
-    SetPowerStats,
-    SetPowerStats::PowerMax
-);
-
-define_upgrade!(
-    POLLUTION_RATE,
-    "Pollution Rate",
-    "Particles per sec",
-    AdditiveEffect::new(2.0, 0.5),
-    ExpCosts::new(30.0, 1.3),
-    SetDustSpawnStats,
-    SetDustSpawnStats::SpawnSpeed
-);
-
-shop_state!(
-    StaticDischargePower -> STATIC_DISCHARGE_POWER
-    EnergyRecovery -> ENERGY_RECOVERY
-    NewDischargePoint -> NEW_DISCHARGE_POINT
-    EnergyCap -> ENERGY_CAP
-    PollutionRate -> POLLUTION_RATE
-);
+            if shop_state.is_maxed(upgrade) {
+                info!("Already fully upgraded.");
+            } else if !shop_state.is_unlocked(upgrade, inventory.collected) {
+                info!("Prerequisites not met.");
+            } else {
+                info!("Not enough data to purchase.");
+            }
+        },
+    );
+    (row, tooltip)
+}
+
+/// Switches between buying one, ten or as many levels as affordable.
+fn buy_amount_button() -> impl Bundle {
+    widget::button_base(
+        BuyAmount::default().label(),
+        TextFont::from_font_size(16.0),
+        |_: Trigger<Pointer<Click>>, mut amount: ResMut<BuyAmount>| {
+            *amount = amount.next();
+        },
+        (
+            Node {
+                width: Val::Px(100.0),
+                height: Val::Px(28.0),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+            BorderRadius::all(Val::Px(5.0)),
+            BuyAmountButton,
+        ),
+    )
+}
@@ -0,0 +1,179 @@
+//! The auto-buyer: once unlocked in the Research Lab, it buys the cheapest
+//! affordable upgrade on a timer, among the ones switched to auto.
+
+use std::collections::BTreeSet;
+
+use crate::demo::{
+    level::CurrentLevel,
+    prestige::MetaProgress,
+    ui::{inventory::Inventory, widget},
+};
+use crate::prelude::*;
+
+use super::{BuyAmount, ShopState, UpgradeRow, catalog::UpgradeCatalog};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<AutoBuyer>();
+    app.init_resource::<AutoBuyTimer>();
+    app.add_observer(
+        |_: Trigger<UnlockAutoBuyer>, mut auto_buyer: ResMut<AutoBuyer>| {
+            auto_buyer.unlocked = true;
+        },
+    );
+    app.add_systems(
+        Update,
+        (
+            auto_buy.run_if(in_state(Screen::Gameplay).and(auto_buyer_unlocked)),
+            update_toggles.run_if(
+                any_with_component::<AutoBuyToggle>.and(
+                    resource_changed::<AutoBuyer>
+                        .or(resource_changed::<ShopState>)
+                        .or(any_match_filter::<Added<AutoBuyToggle>>),
+                ),
+            ),
+        )
+            .in_set(AppSystems::Update),
+    );
+}
+
+/// Seconds between two auto-buys.
+const AUTO_BUY_INTERVAL_SECS: f32 = 2.0;
+
+#[derive(Event, Debug)]
+pub struct UnlockAutoBuyer;
+
+#[derive(Resource, Debug, Default)]
+pub struct AutoBuyer {
+    unlocked: bool,
+    /// Ids of the upgrades it may buy.
+    enabled: BTreeSet<String>,
+}
+
+impl AutoBuyer {
+    pub fn enabled(&self) -> &BTreeSet<String> {
+        &self.enabled
+    }
+
+    /// Replace the upgrades switched to auto, e.g. when restoring a save.
+    pub fn set_enabled(&mut self, enabled: BTreeSet<String>) {
+        self.enabled = enabled;
+    }
+
+    pub fn is_enabled(&self, id: &str) -> bool {
+        self.enabled.contains(id)
+    }
+
+    fn toggle(&mut self, id: &str) {
+        if !self.enabled.remove(id) {
+            self.enabled.insert(id.to_string());
+        }
+    }
+}
+
+#[derive(Resource, Debug)]
+struct AutoBuyTimer(Timer);
+
+impl Default for AutoBuyTimer {
+    fn default() -> Self {
+        Self(Timer::from_seconds(
+            AUTO_BUY_INTERVAL_SECS,
+            TimerMode::Repeating,
+        ))
+    }
+}
+
+fn auto_buyer_unlocked(auto_buyer: Res<AutoBuyer>) -> bool {
+    auto_buyer.unlocked
+}
+
+fn auto_buy(
+    mut commands: Commands,
+    mut timer: ResMut<AutoBuyTimer>,
+    auto_buyer: Res<AutoBuyer>,
+    mut shop_state: ResMut<ShopState>,
+    mut inventory: ResMut<Inventory>,
+    catalog: Res<UpgradeCatalog>,
+    level: Res<CurrentLevel>,
+    meta: Res<MetaProgress>,
+    time: Res<Time>,
+) {
+    if !timer.0.tick(time.delta()).just_finished() {
+        return;
+    }
+    let cheapest = catalog
+        .upgrades
+        .iter()
+        .filter(|upgrade| auto_buyer.is_enabled(&upgrade.id) && level.allows(&upgrade.id))
+        .filter(|upgrade| shop_state.is_available(upgrade, inventory.collected))
+        .min_by_key(|upgrade| shop_state.cost(upgrade));
+    let Some(upgrade) = cheapest else {
+        return;
+    };
+    shop_state.purchase(
+        upgrade,
+        BuyAmount::One,
+        &mut inventory,
+        &meta.bonuses(),
+        &mut commands,
+    );
+}
+
+#[derive(Component, Debug)]
+struct AutoBuyToggle;
+
+/// The switch of an upgrade's row, shown once the auto-buyer is unlocked.
+pub(super) fn auto_buy_toggle(id: String) -> impl Bundle {
+    let row = UpgradeRow(id.clone());
+    widget::button_base(
+        "Auto: off",
+        TextFont::from_font_size(14.0),
+        move |_: Trigger<Pointer<Click>>, mut auto_buyer: ResMut<AutoBuyer>| {
+            auto_buyer.toggle(&id);
+        },
+        (
+            Node {
+                display: Display::None,
+                width: Val::Px(70.0),
+                height: Val::Px(28.0),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+            BorderRadius::all(Val::Px(5.0)),
+            AutoBuyToggle,
+            row,
+        ),
+    )
+}
+
+fn update_toggles(
+    auto_buyer: Res<AutoBuyer>,
+    shop_state: Res<ShopState>,
+    catalog: Res<UpgradeCatalog>,
+    mut toggles: Query<(&mut Node, &UpgradeRow, &Children), With<AutoBuyToggle>>,
+    mut texts: Query<&mut Text>,
+) {
+    for (mut node, row, children) in &mut toggles {
+        let Some(upgrade) = catalog.get(&row.0) else {
+            continue;
+        };
+        // nothing left to buy once maxed
+        let display = if auto_buyer.unlocked && !shop_state.is_maxed(upgrade) {
+            Display::Flex
+        } else {
+            Display::None
+        };
+        if node.display != display {
+            node.display = display;
+        }
+        let label = if auto_buyer.is_enabled(&row.0) {
+            "Auto: on"
+        } else {
+            "Auto: off"
+        };
+        let mut texts = texts.iter_many_mut(children);
+        while let Some(mut text) = texts.fetch_next() {
+            text.set_if_neq(Text(label.to_string()));
+        }
+    }
+}
@@ -0,0 +1,394 @@
+//! The Research Lab catalog, loaded from `assets/data/upgrades.ron`.
+
+use std::collections::{HashMap, HashSet};
+
+use bevy::asset::{AssetLoader, LoadContext, io::Reader};
+use serde::Deserialize;
+
+use crate::{
+    demo::{
+        ChangePlayerStats,
+        currency::{Currency, NumberFormat},
+        gameplay::{
+            DamageType, SetDustSpawnStats, SetPowerStats, SpawnAttacker, TargetingStrategy,
+            UnlockTargetingStrategy,
+        },
+    },
+    prelude::*,
+};
+
+use super::{auto_buy::UnlockAutoBuyer, types::Curve};
+
+#[derive(Resource, AssetCollection)]
+pub struct UpgradeAssets {
+    #[asset(path = "data/upgrades.ron")]
+    pub catalog: Handle<UpgradeCatalog>,
+}
+
+/// Every upgrade sold in the Research Lab, in display order.
+///
+/// Loaded as an asset and mirrored into a resource whenever it (re)loads, so
+/// systems and the headless simulation can read it without asset handles.
+#[derive(Asset, Resource, TypePath, Deserialize, Debug, Clone, Default)]
+pub struct UpgradeCatalog {
+    pub upgrades: Vec<UpgradeDef>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct UpgradeDef {
+    /// Stable key used by [`ShopState`](super::ShopState).
+    pub id: String,
+    pub name: String,
+    pub tips: String,
+    /// Stat value at each upgrade level.
+    pub effect: Curve,
+    pub cost: CostCurve,
+    pub target: UpgradeTarget,
+    /// Highest level that can be bought, unlimited if omitted.
+    #[serde(default)]
+    pub max_level: Option<usize>,
+    /// All of them must be met before the upgrade can be bought.
+    #[serde(default)]
+    pub requires: Vec<Prerequisite>,
+    /// Not listed in the Research Lab until its prerequisites are met.
+    #[serde(default)]
+    pub hidden: bool,
+}
+
+/// Something that must happen before an upgrade can be bought.
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+pub enum Prerequisite {
+    /// Another upgrade bought up to a level.
+    Upgrade { id: String, level: usize },
+    /// Dust data collected from dust this run.
+    Collected(Currency),
+}
+
+/// Price of buying level `n + 1`, the [`Curve`]'s [amount](Curve::amount)
+/// rounded down.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(transparent)]
+pub struct CostCurve(pub Curve);
+
+/// The stat an upgrade changes.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UpgradeTarget {
+    /// Multiplies the energy of every discharge point.
+    AttackEnergy,
+    /// Multiplies the range of every discharge point.
+    AttackRange,
+    /// Each level adds one attacker of the damage type, the effect value is
+    /// only displayed.
+    DischargePoints(DamageType),
+    EnergyRecovery,
+    EnergyCapacity,
+    PollutionRate,
+    /// Lets attackers be switched to the strategy, bought once.
+    UnlockTargeting(TargetingStrategy),
+    /// Unlocks the [auto-buyer](super::auto_buy), bought once.
+    UnlockAutoBuyer,
+}
+
+impl UpgradeDef {
+    /// Highest level that can be bought, `None` if unlimited.
+    pub fn max_level(&self) -> Option<usize> {
+        match (self.max_level, self.target.max_level()) {
+            (Some(max), Some(target_max)) => Some(max.min(target_max)),
+            (max, target_max) => max.or(target_max),
+        }
+    }
+
+    /// Ids and levels of the upgrades it requires.
+    pub fn required_upgrades(&self) -> impl Iterator<Item = (&str, usize)> {
+        self.requires
+            .iter()
+            .filter_map(|prerequisite| match prerequisite {
+                Prerequisite::Upgrade { id, level } => Some((id.as_str(), *level)),
+                Prerequisite::Collected(_) => None,
+            })
+    }
+}
+
+impl Prerequisite {
+    /// What is required, naming upgrades as the `catalog` does.
+    pub fn describe(&self, catalog: &UpgradeCatalog, format: NumberFormat) -> String {
+        match self {
+            Self::Upgrade { id, level } => {
+                let name = catalog.get(id).map_or(id.as_str(), |upgrade| &upgrade.name);
+                format!("{name} {level}")
+            }
+            Self::Collected(amount) => format!("{} dust collected", format.format(*amount)),
+        }
+    }
+}
+
+impl CostCurve {
+    /// factor * base^n
+    pub const fn exponential(factor: f32, base: f32) -> Self {
+        Self(Curve::Multiplicative {
+            initial: factor,
+            ratio: base,
+        })
+    }
+
+    pub fn value(&self, level: usize) -> Currency {
+        self.0.amount(level).floor()
+    }
+
+    /// Price of every level from `n + 1` on.
+    pub fn costs(&self, level: usize) -> impl Iterator<Item = Currency> + '_ {
+        self.0.amounts(level).map(Currency::floor)
+    }
+
+    /// Total price of buying `count` levels from `n + 1` on.
+    pub fn total(&self, level: usize, count: usize) -> Currency {
+        self.costs(level).take(count).sum()
+    }
+}
+
+impl UpgradeTarget {
+    /// Set the stat to `value`.
+    pub fn apply(&self, value: f32, commands: &mut Commands) {
+        match self {
+            Self::AttackEnergy => commands.trigger(ChangePlayerStats::SetEnergyMultiplier(value)),
+            Self::AttackRange => commands.trigger(ChangePlayerStats::SetRangeMultiplier(value)),
+            Self::DischargePoints(damage_type) => commands.trigger(SpawnAttacker {
+                damage_type: *damage_type,
+                ..default()
+            }),
+            Self::EnergyRecovery => commands.trigger(SetPowerStats::RegenSpeed(value)),
+            Self::EnergyCapacity => commands.trigger(SetPowerStats::PowerMax(value)),
+            Self::PollutionRate => commands.trigger(SetDustSpawnStats::SpawnSpeed(value)),
+            Self::UnlockTargeting(strategy) => commands.trigger(UnlockTargetingStrategy(*strategy)),
+            Self::UnlockAutoBuyer => commands.trigger(UnlockAutoBuyer),
+        }
+    }
+
+    /// Highest level that can be bought, `None` if unlimited.
+    pub fn max_level(&self) -> Option<usize> {
+        match self {
+            Self::UnlockTargeting(_) | Self::UnlockAutoBuyer => Some(1),
+            _ => None,
+        }
+    }
+
+    /// What the stat does, shown as the tooltip of its Research Lab row.
+    pub fn explain(&self) -> &'static str {
+        match self {
+            Self::AttackEnergy => {
+                "Energy of every discharge. Stronger discharges also cost more power, \
+                 so this only pays off while power is left over."
+            }
+            Self::AttackRange => {
+                "Reach of every discharge point. The gain depends on where they stand, \
+                 so it is not estimated."
+            }
+            Self::DischargePoints(_) => {
+                "Places one more discharge point. It pays off while power is left over \
+                 and dust slips through."
+            }
+            Self::EnergyRecovery => {
+                "Power regenerated per second. It pays off while discharge points wait \
+                 for power."
+            }
+            Self::EnergyCapacity => {
+                "Most power stored at once. It only helps with bursts of dust, so it is \
+                 not estimated."
+            }
+            Self::PollutionRate => {
+                "Dust spawned per second. More dust to collect, but tougher species \
+                 show up and more of it escapes."
+            }
+            Self::UnlockTargeting(_) => {
+                "Lets discharge points pick their targets this way, right-click one to switch. \
+                 Not estimated."
+            }
+            Self::UnlockAutoBuyer => {
+                "Buys the cheapest affordable upgrade every few seconds, among the ones \
+                 switched to auto. Not estimated."
+            }
+        }
+    }
+
+    /// Whether applying the same value twice is harmless, so the stat can be
+    /// re-applied when the catalog is hot-reloaded.
+    pub fn is_idempotent(&self) -> bool {
+        !matches!(self, Self::DischargePoints(_))
+    }
+}
+
+impl UpgradeCatalog {
+    pub fn get(&self, id: &str) -> Option<&UpgradeDef> {
+        self.upgrades.iter().find(|upgrade| upgrade.id == id)
+    }
+
+    /// Depth of every upgrade in the prerequisite tree: 0 without upgrade
+    /// prerequisites, one more than the deepest one otherwise. `None` if
+    /// they form a cycle or name unknown upgrades.
+    pub fn tiers(&self) -> Option<HashMap<&str, usize>> {
+        self.place_tiers().ok()
+    }
+
+    /// The [`tiers`](Self::tiers), or the first upgrade that can't be placed.
+    fn place_tiers(&self) -> Result<HashMap<&str, usize>, &UpgradeDef> {
+        let mut tiers = HashMap::new();
+        // every pass places the upgrades whose prerequisites are all placed
+        while tiers.len() < self.upgrades.len() {
+            let placed = tiers.len();
+            for upgrade in &self.upgrades {
+                if tiers.contains_key(upgrade.id.as_str()) {
+                    continue;
+                }
+                let tier = upgrade
+                    .required_upgrades()
+                    .map(|(id, _)| tiers.get(id).map(|tier| tier + 1))
+                    .try_fold(0, |deepest, tier: Option<usize>| {
+                        tier.map(|tier| tier.max(deepest))
+                    });
+                if let Some(tier) = tier {
+                    tiers.insert(upgrade.id.as_str(), tier);
+                }
+            }
+            if tiers.len() == placed {
+                let unplaced = self
+                    .upgrades
+                    .iter()
+                    .find(|upgrade| !tiers.contains_key(upgrade.id.as_str()));
+                return Err(unplaced.expect("an upgrade is left to place"));
+            }
+        }
+        Ok(tiers)
+    }
+
+    /// Parse and validate a RON catalog.
+    pub fn from_ron(bytes: &[u8]) -> Result<Self, UpgradeCatalogError> {
+        let catalog: Self = ron::de::from_bytes(bytes)?;
+        catalog.validate()?;
+        Ok(catalog)
+    }
+
+    fn validate(&self) -> Result<(), UpgradeCatalogError> {
+        let mut ids = HashSet::new();
+        for upgrade in &self.upgrades {
+            let invalid = |reason: &str| UpgradeCatalogError::Invalid {
+                id: upgrade.id.clone(),
+                reason: reason.to_string(),
+            };
+            if upgrade.id.is_empty() {
+                return Err(invalid("id is empty"));
+            }
+            if !ids.insert(upgrade.id.as_str()) {
+                return Err(invalid("id is used more than once"));
+            }
+            if upgrade.name.is_empty() {
+                return Err(invalid("name is empty"));
+            }
+            if let Err(reason) = upgrade.effect.check() {
+                return Err(invalid(&format!("effect {reason}")));
+            }
+            if let Err(reason) = upgrade.cost.0.check() {
+                return Err(invalid(&format!("cost {reason}")));
+            }
+            // buying the most affordable levels walks the costs until the
+            // budget runs out, so they must never drop back
+            let checked = upgrade.max_level().unwrap_or(CHECKED_COST_LEVELS);
+            let mut costs = upgrade.cost.costs(0).take(checked);
+            let first = costs.next().unwrap_or_default();
+            if first < Currency::from(1)
+                || costs
+                    .try_fold(first, |last, cost| (cost >= last).then_some(cost))
+                    .is_none()
+            {
+                return Err(invalid("cost must start at 1 or more and never decrease"));
+            }
+            if upgrade.max_level == Some(0) {
+                return Err(invalid("max_level must be at least 1"));
+            }
+        }
+        for upgrade in &self.upgrades {
+            for (id, level) in upgrade.required_upgrades() {
+                let invalid = |reason: String| UpgradeCatalogError::Invalid {
+                    id: upgrade.id.clone(),
+                    reason,
+                };
+                let Some(required) = self.get(id) else {
+                    return Err(invalid(format!("requires unknown upgrade `{id}`")));
+                };
+                if level == 0 || required.max_level().is_some_and(|max| level > max) {
+                    return Err(invalid(format!(
+                        "requires a level of `{id}` that can't be bought"
+                    )));
+                }
+            }
+        }
+        if let Err(upgrade) = self.place_tiers() {
+            return Err(UpgradeCatalogError::Invalid {
+                id: upgrade.id.clone(),
+                reason: "prerequisites form a cycle".to_string(),
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Levels whose costs are checked when an upgrade has no max level.
+const CHECKED_COST_LEVELS: usize = 1000;
+
+#[derive(thiserror::Error, Debug)]
+pub enum UpgradeCatalogError {
+    #[error("could not read upgrade catalog: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("could not parse upgrade catalog: {0}")]
+    Ron(#[from] ron::error::SpannedError),
+    #[error("invalid upgrade `{id}`: {reason}")]
+    Invalid { id: String, reason: String },
+}
+
+#[derive(Default)]
+pub struct UpgradeCatalogLoader;
+
+impl AssetLoader for UpgradeCatalogLoader {
+    type Asset = UpgradeCatalog;
+    type Settings = ();
+    type Error = UpgradeCatalogError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        UpgradeCatalog::from_ron(&bytes)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["ron"]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn total_sums_the_next_levels() {
+        let cost = CostCurve::exponential(10.0, 2.0);
+        // levels 3, 4 and 5 after level 2
+        assert_eq!(
+            cost.costs(2).take(3).collect::<Vec<_>>(),
+            [40u32, 80, 160].map(Currency::from)
+        );
+        assert_eq!(cost.total(2, 3), Currency::from(40u32 + 80 + 160));
+        assert_eq!(cost.total(0, 1), cost.value(0));
+        assert_eq!(cost.total(2, 0), Currency::ZERO);
+    }
+
+    #[test]
+    fn costs_are_whole_amounts() {
+        let cost = CostCurve::exponential(10.0, 1.15);
+        assert_eq!(cost.value(1), Currency::from(11u32));
+        assert_eq!(cost.total(0, 2), Currency::from(21u32));
+    }
+}
@@ -0,0 +1,135 @@
+//! Estimated effect of the next level of each upgrade, shown below its tip.
+//!
+//! A rough steady-state model of the economy: dust is cleared as fast as the
+//! discharge points can spend power, or as fast as it spawns.
+
+use crate::demo::{
+    GameArea, PlayerStats,
+    currency::{Currency, NumberFormat},
+    gameplay::{Attacker, AttackerStats, DustAverages, DustSpawner, Power},
+    prestige::{MetaBonuses, MetaProgress},
+    ui::widget::RowNote,
+};
+use crate::prelude::*;
+
+use super::{
+    ShopState, UpgradeRow,
+    catalog::{UpgradeCatalog, UpgradeDef, UpgradeTarget},
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        update_estimates
+            .in_set(AppSystems::Update)
+            .run_if(any_with_component::<RowNote>),
+    );
+}
+
+/// How to read an estimate, added to every upgrade tooltip.
+pub(super) const ESTIMATE_HINT: &str = "Below: the collect rate the next level adds right now, \
+     and how long that takes to earn its cost back.";
+
+#[derive(Debug, Clone, Copy)]
+struct EconomyModel {
+    /// Power regenerated per second.
+    regen: f32,
+    /// Energy per second the discharge points in the arena can spend, before
+    /// the energy multiplier.
+    discharge: f32,
+    energy_multiplier: f32,
+    /// Dust spawned per second, which is also the pollution rate.
+    spawn_speed: f32,
+}
+
+impl EconomyModel {
+    /// Dust data collected per second.
+    fn collect_rate(&self) -> f32 {
+        let Some(dust) = DustAverages::at(self.spawn_speed) else {
+            return 0.0;
+        };
+        // every bit of energy spent ends up as damage
+        let damage = self.regen.min(self.discharge * self.energy_multiplier);
+        (damage / dust.health).min(self.spawn_speed) * dust.reward
+    }
+
+    /// The model once `target` is set to `value`, `None` if it does not model
+    /// the stat.
+    fn with(mut self, target: UpgradeTarget, value: f32) -> Option<Self> {
+        match target {
+            UpgradeTarget::AttackEnergy => self.energy_multiplier = value,
+            UpgradeTarget::DischargePoints(damage_type) => {
+                let stats = AttackerStats::new(damage_type);
+                self.discharge += stats.energy / stats.interval;
+            }
+            UpgradeTarget::EnergyRecovery => self.regen = value,
+            UpgradeTarget::PollutionRate => self.spawn_speed = value,
+            UpgradeTarget::AttackRange
+            | UpgradeTarget::EnergyCapacity
+            | UpgradeTarget::UnlockTargeting(_)
+            | UpgradeTarget::UnlockAutoBuyer => return None,
+        }
+        Some(self)
+    }
+
+    fn estimate(
+        &self,
+        shop_state: &ShopState,
+        upgrade: &UpgradeDef,
+        bonuses: &MetaBonuses,
+        format: NumberFormat,
+    ) -> String {
+        if shop_state.is_maxed(upgrade) {
+            return String::new();
+        }
+        let level = shop_state.level(&upgrade.id);
+        let value = bonuses.modify(upgrade.target, upgrade.effect.value(level + 1));
+        let Some(upgraded) = self.with(upgrade.target, value) else {
+            return "No estimate".to_string();
+        };
+        let gain = upgraded.collect_rate() - self.collect_rate();
+        if gain < 0.05 {
+            return "~ no gain right now".to_string();
+        }
+        let payback = shop_state.cost(upgrade).to_f64() / gain as f64;
+        let gain = format.format(Currency::from(gain));
+        format!("~ +{gain} dust/s, pays back in {payback:.0}s")
+    }
+}
+
+fn update_estimates(
+    mut estimates: Query<(&mut Text, &UpgradeRow), With<RowNote>>,
+    shop_state: Res<ShopState>,
+    catalog: Res<UpgradeCatalog>,
+    meta: Res<MetaProgress>,
+    power: Res<Power>,
+    player_stats: Res<PlayerStats>,
+    attackers: Query<(&AttackerStats, &Transform), With<Attacker>>,
+    area: Res<GameArea>,
+    spawner: Single<&DustSpawner>,
+    format: Res<NumberFormat>,
+) {
+    let model = EconomyModel {
+        regen: power.regen_speed(),
+        // discharge points outside the arena never fire
+        discharge: attackers
+            .iter()
+            .filter(|(_, transform)| area.contains(transform.translation.truncate()))
+            .map(|(stats, _)| stats.energy / stats.interval)
+            .sum(),
+        energy_multiplier: player_stats.energy_multiplier,
+        spawn_speed: spawner.speed(),
+    };
+    let bonuses = meta.bonuses();
+    for (mut text, row) in &mut estimates {
+        let Some(upgrade) = catalog.get(&row.0) else {
+            continue;
+        };
+        text.set_if_neq(Text(model.estimate(
+            &shop_state,
+            upgrade,
+            &bonuses,
+            *format,
+        )));
+    }
+}
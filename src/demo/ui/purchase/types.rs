@@ -1,135 +1,333 @@
-use crate::{
-    demo::ui::{
-        inventory,
-        purchase::{PurchaseUIChanged, ShopState, UpgradeItems},
-        widget,
+//! Curves giving a value per upgrade level, shared by effects and costs.
+
+use serde::Deserialize;
+
+use crate::demo::currency::Currency;
+
+/// Value at each level `n`, starting from level 0.
+///
+/// Every curve is defined by its closed form in [`Curve::value`], walking its
+/// [`Curve::amounts`] only moves the level along.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub enum Curve {
+    /// v_n = initial + n * increment
+    Additive { initial: f32, increment: f32 },
+    /// v_n = initial * ratio^n
+    Multiplicative { initial: f32, ratio: f32 },
+    /// v_n = c_0 + c_1 * n + c_2 * n^2 + ...
+    Polynomial { coefficients: Vec<f32> },
+    /// v_n = initial + scale * ln(1 + n), diminishing returns.
+    Logarithmic { initial: f32, scale: f32 },
+    /// Linear between `(level, value)` points sorted by level, flat before the
+    /// first one and after the last one. A point per level makes a table.
+    Piecewise { points: Vec<(usize, f32)> },
+    /// `curve` until it reaches `cap`, then v_n = cap * (u_n / cap)^power,
+    /// slower growth for a power below 1.
+    SoftCap {
+        curve: Box<Curve>,
+        cap: f32,
+        power: f32,
     },
-    prelude::*,
-};
-
-pub trait Upgrades {
-    type Effect: Event + Clone;
-    fn name(&self) -> &'static str;
-    fn tips(&self) -> &'static str;
-    fn get_current_upgrade(&self, level: usize) -> Option<(Self::Effect, (f32, f32), u32)>;
-    fn row(&self, level: usize, item: UpgradeItems) -> Option<impl Bundle> {
-        if let Some((effect, (prev, new), cost)) = self.get_current_upgrade(level) {
-            Some(widget::row(
-                self.name(),
-                format!("{}: {:.1}->{:.1}", self.tips(), prev, new),
-                cost,
-                move |_t: Trigger<Pointer<Click>>,
-                      mut inventory: ResMut<inventory::Inventory>,
-                      mut commands: Commands,
-                      mut shop_state: ResMut<ShopState>| {
-                    if inventory.dust_data >= cost {
-                        inventory.dust_data -= cost;
-                        shop_state.update_by_event(item);
-                        commands.trigger(effect.clone());
-                        commands.trigger(PurchaseUIChanged);
-                    } else {
-                        info!("Not enough data to purchase.");
+    /// `curve` multiplied by `multiplier` once more every `every` levels.
+    Milestones {
+        curve: Box<Curve>,
+        every: usize,
+        multiplier: f32,
+    },
+}
+
+impl Curve {
+    pub fn value(&self, level: usize) -> f32 {
+        match self {
+            Self::Additive { initial, increment } => initial + increment * level as f32,
+            Self::Multiplicative { initial, ratio } => initial * ratio.powi(level as i32),
+            Self::Polynomial { coefficients } => coefficients
+                .iter()
+                .rev()
+                .fold(0.0, |value, coefficient| value * level as f32 + coefficient),
+            Self::Logarithmic { initial, scale } => initial + scale * (level as f32).ln_1p(),
+            Self::Piecewise { points } => {
+                let after = points.partition_point(|(at, _)| *at <= level);
+                match (after.checked_sub(1).map(|i| points[i]), points.get(after)) {
+                    (Some((from, start)), Some(&(to, end))) => {
+                        let t = (level - from) as f32 / (to - from) as f32;
+                        start + (end - start) * t
                     }
-                },
-            ))
-        } else {
-            None
+                    (Some((_, value)), None) | (None, Some(&(_, value))) => value,
+                    (None, None) => 0.0,
+                }
+            }
+            Self::SoftCap { curve, cap, power } => {
+                let value = curve.value(level);
+                if value > *cap {
+                    cap * (value / cap).powf(*power)
+                } else {
+                    value
+                }
+            }
+            Self::Milestones {
+                curve,
+                every,
+                multiplier,
+            } => curve.value(level) * multiplier.powi((level / every) as i32),
         }
     }
-}
 
-/// factor * base^k
-#[derive(Debug, Clone)]
-pub struct ExpCosts {
-    factor: f32,
-    base: f32,
-    k: usize,
-}
+    /// [`value`](Self::value) as a [`Currency`], for curves that grow past
+    /// what a float holds.
+    pub fn amount(&self, level: usize) -> Currency {
+        match self {
+            Self::Multiplicative { initial, ratio } => {
+                Currency::from(*initial).scale_pow(*ratio as f64, level)
+            }
+            Self::SoftCap { curve, cap, power } => {
+                let amount = curve.amount(level);
+                let cap = Currency::from(*cap);
+                if amount <= cap {
+                    return amount;
+                }
+                let ratio = amount.to_f64() / cap.to_f64();
+                if ratio.is_finite() {
+                    cap.scale(ratio.powf(*power as f64))
+                } else {
+                    Currency::from_log10(
+                        cap.log10() + (amount.log10() - cap.log10()) * *power as f64,
+                    )
+                }
+            }
+            Self::Milestones {
+                curve,
+                every,
+                multiplier,
+            } => curve
+                .amount(level)
+                .scale_pow(*multiplier as f64, level / every),
+            _ => Currency::from(self.value(level)),
+        }
+    }
 
-impl ExpCosts {
-    pub const fn new(factor: f32, base: f32) -> Self {
-        Self { factor, base, k: 0 }
+    /// The amounts from `level` on.
+    pub fn amounts(&self, level: usize) -> CurveAmounts<'_> {
+        CurveAmounts { curve: self, level }
+    }
+
+    /// Why the curve can't be used, if it can't.
+    pub fn check(&self) -> Result<(), String> {
+        let finite = |values: &[f32]| values.iter().all(|value| value.is_finite());
+        match self {
+            Self::Additive { initial, increment } => {
+                if !finite(&[*initial, *increment]) {
+                    return Err("values must be finite".to_string());
+                }
+            }
+            Self::Multiplicative { initial, ratio } => {
+                if !finite(&[*initial, *ratio]) || *ratio <= 0.0 {
+                    return Err("ratio must be a positive number".to_string());
+                }
+            }
+            Self::Polynomial { coefficients } => {
+                if coefficients.is_empty() || !finite(coefficients) {
+                    return Err("coefficients must be finite, at least one".to_string());
+                }
+            }
+            Self::Logarithmic { initial, scale } => {
+                if !finite(&[*initial, *scale]) {
+                    return Err("values must be finite".to_string());
+                }
+            }
+            Self::Piecewise { points } => {
+                if points.is_empty() || !points.iter().all(|(_, value)| value.is_finite()) {
+                    return Err("points must be finite, at least one".to_string());
+                }
+                if !points.windows(2).all(|pair| pair[0].0 < pair[1].0) {
+                    return Err("points must be sorted by level, one per level".to_string());
+                }
+            }
+            Self::SoftCap { curve, cap, power } => {
+                curve.check()?;
+                if !(cap.is_finite() && *cap > 0.0) {
+                    return Err("soft cap must be a positive number".to_string());
+                }
+                if !(power.is_finite() && *power > 0.0) {
+                    return Err("soft cap power must be a positive number".to_string());
+                }
+            }
+            Self::Milestones {
+                curve,
+                every,
+                multiplier,
+            } => {
+                curve.check()?;
+                if *every == 0 {
+                    return Err("milestones must be at least one level apart".to_string());
+                }
+                if !(multiplier.is_finite() && *multiplier > 0.0) {
+                    return Err("milestone multiplier must be a positive number".to_string());
+                }
+            }
+        }
+        Ok(())
     }
 }
 
-impl Iterator for ExpCosts {
-    type Item = u32;
+/// Iterator over a [`Curve`]'s amounts, one level at a time.
+#[derive(Debug, Clone)]
+pub struct CurveAmounts<'a> {
+    curve: &'a Curve,
+    level: usize,
+}
+
+impl Iterator for CurveAmounts<'_> {
+    type Item = Currency;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let cost = self.factor * self.base.powi(self.k as i32);
-        self.k += 1;
-        Some(cost as u32)
+        self.nth(0)
     }
 
+    /// Skips straight to the value `n` levels ahead, then moves past it like
+    /// `n + 1` calls to `next` would.
     fn nth(&mut self, n: usize) -> Option<Self::Item> {
-        let cost = self.factor * self.base.powi((self.k + n) as i32);
-        self.k += n;
-        Some(cost as u32)
+        let level = self.level.checked_add(n)?;
+        self.level = level.checked_add(1)?;
+        Some(self.curve.amount(level))
     }
 }
 
-/// v_n = v_0 + k * dv
-#[derive(Debug, Clone)]
-pub struct AdditiveEffect {
-    initial_value: f32,
-    increment: f32,
-    k: usize,
-}
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-impl AdditiveEffect {
-    pub const fn new(initial_value: f32, increment: f32) -> Self {
-        Self {
-            initial_value,
-            increment,
-            k: 0,
+    /// `nth(k)` from `start` gives what `k + 1` calls to `next` end on, and
+    /// leaves the iterator where they leave it.
+    fn assert_nth_matches_next(curve: &Curve, start: usize) {
+        for k in 0..12 {
+            let mut stepped = curve.amounts(start);
+            let expected = (0..=k).map(|_| stepped.next()).last().flatten();
+            let mut skipped = curve.amounts(start);
+            assert_eq!(skipped.nth(k), expected, "{curve:?} from {start}, k = {k}");
+            assert_eq!(
+                skipped.level, stepped.level,
+                "{curve:?} from {start}, k = {k}"
+            );
+            assert_eq!(
+                skipped.next(),
+                stepped.next(),
+                "{curve:?} from {start}, k = {k}"
+            );
         }
     }
-}
 
-impl Iterator for AdditiveEffect {
-    type Item = f32;
+    /// The first amounts from `start`, as floats.
+    fn amounts(curve: &Curve, start: usize, count: usize) -> Vec<f64> {
+        curve
+            .amounts(start)
+            .take(count)
+            .map(Currency::to_f64)
+            .collect()
+    }
 
-    fn next(&mut self) -> Option<Self::Item> {
-        let value = self.initial_value + self.increment * (self.k as f32);
-        self.k += 1;
-        Some(value)
+    #[test]
+    fn additive() {
+        let curve = Curve::Additive {
+            initial: 2.0,
+            increment: 3.0,
+        };
+        assert_eq!(amounts(&curve, 0, 4), [2.0, 5.0, 8.0, 11.0]);
+        assert_eq!(amounts(&curve, 10, 2), [32.0, 35.0]);
+        assert_nth_matches_next(&curve, 0);
+        assert_nth_matches_next(&curve, 7);
     }
 
-    fn nth(&mut self, n: usize) -> Option<Self::Item> {
-        let value = self.initial_value + self.increment * (self.k + n) as f32;
-        self.k += n;
-        Some(value)
+    #[test]
+    fn multiplicative() {
+        let curve = Curve::Multiplicative {
+            initial: 10.0,
+            ratio: 1.5,
+        };
+        assert_eq!(amounts(&curve, 0, 4), [10.0, 15.0, 22.5, 33.75]);
+        // far past what a float holds
+        assert!((curve.amount(2000).log10() - (1.0 + 2000.0 * 1.5f64.log10())).abs() < 1e-6);
+        assert_nth_matches_next(&curve, 0);
+        assert_nth_matches_next(&curve, 7);
     }
-}
 
-/// v_n = v_0 * r^k
-#[derive(Debug, Clone)]
-pub struct MultiplicativeEffect {
-    initial_value: f32,
-    ratio: f32,
-    k: usize,
-}
-impl MultiplicativeEffect {
-    pub const fn new(initial_value: f32, ratio: f32) -> Self {
-        Self {
-            initial_value,
-            ratio,
-            k: 0,
+    #[test]
+    fn polynomial() {
+        // 1 + 2n + 3n^2
+        let curve = Curve::Polynomial {
+            coefficients: vec![1.0, 2.0, 3.0],
+        };
+        assert_eq!(amounts(&curve, 0, 4), [1.0, 6.0, 17.0, 34.0]);
+        assert_nth_matches_next(&curve, 0);
+        assert_nth_matches_next(&curve, 7);
+    }
+
+    #[test]
+    fn logarithmic() {
+        let curve = Curve::Logarithmic {
+            initial: 1.0,
+            scale: 4.0,
+        };
+        assert_eq!(curve.value(0), 1.0);
+        for level in [1, 3, 99] {
+            let expected = 1.0 + 4.0 * (1.0 + level as f32).ln();
+            assert!(
+                (curve.value(level) - expected).abs() < 1e-5,
+                "level {level}"
+            );
         }
+        assert_nth_matches_next(&curve, 0);
+        assert_nth_matches_next(&curve, 7);
     }
-}
-impl Iterator for MultiplicativeEffect {
-    type Item = f32;
 
-    fn next(&mut self) -> Option<Self::Item> {
-        let value = self.initial_value * self.ratio.powi(self.k as i32);
-        self.k += 1;
-        Some(value)
+    #[test]
+    fn piecewise() {
+        let curve = Curve::Piecewise {
+            points: vec![(2, 10.0), (5, 40.0), (8, 20.0)],
+        };
+        // flat before the first point, linear between points, flat after the last
+        assert_eq!(curve.value(0), 10.0);
+        assert_eq!(curve.value(3), 20.0);
+        assert_eq!(curve.value(9), 20.0);
+        for start in [0, 3, 9] {
+            assert_nth_matches_next(&curve, start);
+        }
     }
 
-    fn nth(&mut self, n: usize) -> Option<Self::Item> {
-        let value = self.initial_value * self.ratio.powi((self.k + n) as i32);
-        self.k += n;
-        Some(value)
+    #[test]
+    fn soft_cap() {
+        let curve = Curve::SoftCap {
+            curve: Box::new(Curve::Additive {
+                initial: 0.0,
+                increment: 10.0,
+            }),
+            cap: 50.0,
+            power: 0.5,
+        };
+        // below the cap from 0 to 5, above it from 6 on
+        assert_eq!(curve.value(4), 40.0);
+        assert!(curve.value(8) < 80.0);
+        for start in [0, 4, 6] {
+            assert_nth_matches_next(&curve, start);
+        }
+    }
+
+    #[test]
+    fn milestones() {
+        let curve = Curve::Milestones {
+            curve: Box::new(Curve::Additive {
+                initial: 1.0,
+                increment: 1.0,
+            }),
+            every: 4,
+            multiplier: 2.0,
+        };
+        // doubled at level 4 and again at level 8
+        assert_eq!(curve.value(3), 4.0);
+        assert_eq!(curve.value(4), 10.0);
+        assert_eq!(curve.value(8), 36.0);
+        for start in [0, 2, 4] {
+            assert_nth_matches_next(&curve, start);
+        }
     }
 }
@@ -8,10 +8,63 @@ use crate::theme::palette::*;
 use crate::theme::prelude::*;
 pub use crate::theme::widget::*;
 
+/// The tip text of a [`row`].
+#[derive(Component, Debug, Clone, Copy)]
+pub struct RowTip;
+
+/// The note text of a [`row_with_note`].
+#[derive(Component, Debug, Clone, Copy)]
+pub struct RowNote;
+
+/// The cost text of a [`row`].
+#[derive(Component, Debug, Clone, Copy)]
+pub struct RowCost;
+
+/// The upgrade button of a [`row`].
+#[derive(Component, Debug, Clone, Copy)]
+pub struct RowButton;
+
 pub fn row<E, B, M, I>(
     item_name: impl Into<String>,
     tip: impl Into<String>,
-    price: u32,
+    cost: impl Into<String>,
+    action: I,
+) -> impl Bundle
+where
+    E: Event,
+    B: Bundle,
+    I: IntoObserverSystem<E, B, M>,
+{
+    row_base(upgrade_text(item_name, tip, ()), cost, (), action)
+}
+
+/// A [`row`] with a smaller third line of text. Its tip, note, cost and button
+/// all carry `marker` besides their [`RowTip`], [`RowNote`], [`RowCost`] and
+/// [`RowButton`], so they can be updated in place.
+pub fn row_with_note<E, B, M, I>(
+    item_name: impl Into<String>,
+    tip: impl Into<String>,
+    cost: impl Into<String>,
+    marker: impl Bundle + Clone,
+    action: I,
+) -> impl Bundle
+where
+    E: Event,
+    B: Bundle,
+    I: IntoObserverSystem<E, B, M>,
+{
+    row_base(
+        upgrade_text_with_note(item_name, tip, marker.clone()),
+        cost,
+        marker,
+        action,
+    )
+}
+
+fn row_base<E, B, M, I>(
+    text: impl Bundle,
+    cost: impl Into<String>,
+    marker: impl Bundle + Clone,
     action: I,
 ) -> impl Bundle
 where
@@ -23,7 +76,7 @@ where
         Name::new("PurchaseRow"),
         Node {
             width: Val::Percent(100.0),
-            height: Val::Px(50.0),
+            min_height: Val::Px(50.0),
             column_gap: Px(10.0),
             flex_direction: FlexDirection::Row,
             justify_content: JustifyContent::SpaceBetween,
@@ -32,11 +85,44 @@ where
         },
         BackgroundColor::from(PURCHASE_ROW_BACKGROUND),
         BorderRadius::all(Val::Px(5.0)),
-        children![upgrade_text(item_name, tip), upgrade_button(price, action),],
+        children![text, upgrade_button(cost, marker, action),],
     )
 }
 
-fn upgrade_text(item_name: impl Into<String>, tip: impl Into<String>) -> impl Bundle {
+fn upgrade_text(
+    item_name: impl Into<String>,
+    tip: impl Into<String>,
+    marker: impl Bundle,
+) -> impl Bundle {
+    (
+        upgrade_text_node(),
+        children![item_name_text(item_name), tip_text(tip, marker)],
+    )
+}
+
+fn upgrade_text_with_note(
+    item_name: impl Into<String>,
+    tip: impl Into<String>,
+    marker: impl Bundle + Clone,
+) -> impl Bundle {
+    (
+        upgrade_text_node(),
+        children![
+            item_name_text(item_name),
+            tip_text(tip, marker.clone()),
+            (
+                Name::new("Note"),
+                Text::default(),
+                TextFont::from_font_size(14.0),
+                TextColor(LABEL_TEXT),
+                RowNote,
+                marker,
+            ),
+        ],
+    )
+}
+
+fn upgrade_text_node() -> impl Bundle {
     (
         Name::new("UpgradeText"),
         Node {
@@ -45,28 +131,39 @@ fn upgrade_text(item_name: impl Into<String>, tip: impl Into<String>) -> impl Bu
             align_items: AlignItems::FlexStart,
             ..default()
         },
-        children![
-            (
-                Name::new("Item Name"),
-                Text::new(item_name.into()),
-                TextFont::from_font_size(20.0),
-            ),
-            (
-                Name::new("Tip"),
-                Text::new(tip.into()),
-                TextFont::from_font_size(16.0),
-            ),
-        ],
     )
 }
 
-pub fn upgrade_button<E, B, M, I>(cost: u32, action: I) -> impl Bundle
+fn item_name_text(item_name: impl Into<String>) -> impl Bundle {
+    (
+        Name::new("Item Name"),
+        Text::new(item_name.into()),
+        TextFont::from_font_size(20.0),
+    )
+}
+
+fn tip_text(tip: impl Into<String>, marker: impl Bundle) -> impl Bundle {
+    (
+        Name::new("Tip"),
+        Text::new(tip.into()),
+        TextFont::from_font_size(16.0),
+        RowTip,
+        marker,
+    )
+}
+
+pub fn upgrade_button<E, B, M, I>(
+    cost: impl Into<String>,
+    marker: impl Bundle + Clone,
+    action: I,
+) -> impl Bundle
 where
     E: Event,
     B: Bundle,
     I: IntoObserverSystem<E, B, M>,
 {
     let action = IntoObserverSystem::into_system(action);
+    let cost = cost.into();
     (
         Name::new("UpgradeButton"),
         Node {
@@ -88,12 +185,10 @@ where
                         ..default()
                     },
                     Button,
-                    InteractionPalette {
-                        none: BUTTON_BACKGROUND,
-                        hovered: BUTTON_HOVERED_BACKGROUND,
-                        pressed: BUTTON_PRESSED_BACKGROUND,
-                    },
+                    InteractionPalette::BUTTON,
                     BorderRadius::all(Px(5.0)),
+                    RowButton,
+                    marker.clone(),
                     children![(
                         Name::new("Button Text"),
                         Text::new("Upgrade"),
@@ -105,6 +200,8 @@ where
                 Name::new("Cost Text"),
                 Text::new(format!("Cost: {}", cost)),
                 TextFont::from_font_size(16.0),
+                RowCost,
+                marker,
             ));
         })),
     )
@@ -8,7 +8,10 @@ use bevy::{
 };
 use bevy_inspector_egui::{bevy_egui::EguiPlugin, quick::WorldInspectorPlugin};
 
-use crate::{demo::Inventory, screens::Screen};
+use crate::{
+    demo::{Inventory, currency::Currency},
+    screens::Screen,
+};
 
 pub(super) fn plugin(app: &mut App) {
     // // inspector egui
@@ -39,6 +42,6 @@ fn toggle_debug_ui(mut options: ResMut<UiDebugOptions>) {
 }
 
 fn dev_add_dust_data(mut inventory: ResMut<Inventory>) {
-    inventory.dust_data += 100;
+    inventory.dust_data += Currency::from(100);
     info!("Added 100 dust. Current balance: {}", inventory.dust_data);
 }
@@ -10,6 +10,7 @@ mod demo;
 mod dev_tools;
 mod menus;
 mod screens;
+mod storage;
 mod theme;
 mod utils;
 mod visual_effect;
@@ -34,6 +35,10 @@ mod prelude {
 }
 
 fn main() -> AppExit {
+    #[cfg(feature = "headless")]
+    if let Some(exit) = demo::simulation::run_from_args() {
+        return exit;
+    }
     App::new().add_plugins(AppPlugin).run()
 }
 
@@ -65,7 +70,9 @@ impl Plugin for AppPlugin {
         // setup asset loader
         app.init_state::<AssetsState>();
         app.add_loading_state(
-            LoadingState::new(AssetsState::Loading).continue_to_state(AssetsState::Done),
+            LoadingState::new(AssetsState::Loading)
+                .continue_to_state(AssetsState::Done)
+                .on_failure_continue_to_state(AssetsState::Failed),
         );
 
         // add third-party plugins
@@ -84,31 +91,37 @@ impl Plugin for AppPlugin {
             camera::plugin,
         ));
 
-        // Order new `AppSystems` variants by adding them here:
-        app.configure_sets(
-            Update,
-            (
-                AppSystems::TickTimers,
-                AppSystems::RecordInput,
-                AppSystems::Update,
-                AppSystems::Cleanup,
-            )
-                .chain()
-                .in_set(PausableSystems),
-        );
-
-        // Set up the `Pause` state.
-        app.init_state::<Pause>();
-        app.configure_sets(Update, PausableSystems.run_if(in_state(Pause(false))));
+        app.add_plugins(system_sets);
 
         // back ground color : sky blue
         app.insert_resource(ClearColor(Color::srgb(0.58, 0.686, 0.773)));
     }
 }
 
+/// Order the [`AppSystems`] sets and set up the [`Pause`] state.
+/// Shared with the headless simulation, which runs without [`AppPlugin`].
+fn system_sets(app: &mut App) {
+    // Order new `AppSystems` variants by adding them here:
+    app.configure_sets(
+        Update,
+        (
+            AppSystems::TickTimers,
+            AppSystems::RecordInput,
+            AppSystems::Update,
+            AppSystems::Cleanup,
+        )
+            .chain()
+            .in_set(PausableSystems),
+    );
+
+    // Set up the `Pause` state.
+    app.init_state::<Pause>();
+    app.configure_sets(Update, PausableSystems.run_if(in_state(Pause(false))));
+}
+
 /// High-level groupings of systems for the app in the `Update` schedule.
 /// When adding a new variant, make sure to order it in the `configure_sets`
-/// call above.
+/// call in [`system_sets`].
 #[derive(SystemSet, Debug, Clone, Copy, Eq, PartialEq, Hash, PartialOrd, Ord)]
 pub enum AppSystems {
     /// Tick timers.
@@ -135,4 +148,10 @@ pub enum AssetsState {
     #[default]
     Loading,
     Done,
+    /// An asset could not be loaded, the game can't start.
+    Failed,
 }
+
+/// Why the game could not start, shown once [`AssetsState::Failed`] is entered.
+#[derive(Resource, Debug, Default)]
+pub struct LoadErrors(pub Vec<String>);
@@ -0,0 +1,67 @@
+//! The achievements menu, opened from the main menu.
+
+use bevy::{ecs::spawn::SpawnWith, input::common_conditions::input_just_pressed};
+
+use crate::prelude::*;
+
+use crate::{
+    demo::achievements::{Achievement, AchievementProgress},
+    menus::Menu,
+    theme::widget,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Menu::Achievements), spawn_achievements_menu);
+    app.add_systems(
+        Update,
+        go_back.run_if(in_state(Menu::Achievements).and(input_just_pressed(KeyCode::Escape))),
+    );
+}
+
+fn spawn_achievements_menu(mut commands: Commands, progress: Res<AchievementProgress>) {
+    let rows = Achievement::ALL.map(|achievement| {
+        let spec = achievement.spec();
+        if progress.is_unlocked(achievement) {
+            return format!("[x] {}: {}", spec.name, spec.description);
+        }
+        match spec.requirement.progress(&progress.milestones) {
+            Some((current, target)) => format!(
+                "[ ] {}: {} ({}/{target})",
+                spec.name,
+                spec.description,
+                current.min(target)
+            ),
+            None => format!("[ ] {}: {}", spec.name, spec.description),
+        }
+    });
+    let summary = format!(
+        "{}/{} unlocked",
+        progress.unlocked_count(),
+        Achievement::ALL.len()
+    );
+    commands.spawn((
+        widget::ui_root("Achievements Menu"),
+        GlobalZIndex(2),
+        StateScoped(Menu::Achievements),
+        Children::spawn(SpawnWith(move |parent: &mut ChildSpawner| {
+            parent.spawn(widget::header("Achievements"));
+            parent.spawn(widget::label(summary));
+            for row in rows {
+                parent.spawn((
+                    Name::new("Achievement"),
+                    Text(row),
+                    TextFont::from_font_size(18.0),
+                ));
+            }
+            parent.spawn(widget::button("Back", go_back_on_click));
+        })),
+    ));
+}
+
+fn go_back_on_click(_: Trigger<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Main);
+}
+
+fn go_back(mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Main);
+}
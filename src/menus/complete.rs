@@ -1,13 +1,29 @@
 //! The Complete Screen.
 
-use crate::prelude::*;
+use std::time::Duration;
 
-use crate::{menus::Menu, screens::Screen, theme::widget};
+use crate::prelude::*;
 
-#[derive(Event, Debug)]
-pub struct CompleteTheGame;
+use crate::{
+    demo::{
+        UpgradeCatalog,
+        currency::NumberFormat,
+        level::{CurrentLevel, catalog::Objective},
+        objectives::ObjectiveProgress,
+    },
+    menus::{Menu, statistics::open_statistics_menu},
+    screens::Screen,
+    theme::widget,
+};
 
-pub const COMPLETE_COLLECTION_RATE: f64 = 6.0 * 8.0;
+/// Sent once per run, when enough of the level's objectives are met.
+#[derive(Event, Debug, Clone)]
+pub struct CompleteTheGame {
+    /// The met objectives, in the level's order.
+    pub objectives: Vec<Objective>,
+    /// Play time of the run, pauses excluded.
+    pub elapsed: Duration,
+}
 
 pub(super) fn plugin(app: &mut App) {
     app.add_systems(OnEnter(Menu::Complete), spawn_complete_menu);
@@ -15,8 +31,27 @@ pub(super) fn plugin(app: &mut App) {
     app.add_event::<CompleteTheGame>();
 }
 
-fn spawn_complete_menu(mut commands: Commands) {
+fn spawn_complete_menu(
+    mut commands: Commands,
+    level: Res<CurrentLevel>,
+    progress: Res<ObjectiveProgress>,
+    catalog: Res<UpgradeCatalog>,
+    format: Res<NumberFormat>,
+) {
     info!("Opening complete menu");
+    let seconds = progress.completed_at().unwrap_or(progress.elapsed) as u32;
+    let summary = format!(
+        "{} complete after {}m {:02}s: {}",
+        level.name,
+        seconds / 60,
+        seconds % 60,
+        progress
+            .met_objectives(&level.objectives)
+            .iter()
+            .map(|objective| objective.describe(&catalog, *format))
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
     commands.spawn((
         widget::ui_root("Complete Menu"),
         GlobalZIndex(2),
@@ -35,8 +70,11 @@ fn spawn_complete_menu(mut commands: Commands) {
             },
             children![
                 widget::header("Mission Complete: The Dark Truth"),
+                widget::label(summary),
                 text_block(),
                 widget::button("Continue", close_menu),
+                widget::button("Statistics", open_statistics_menu(Menu::Complete)),
+                widget::button("Prestige", open_prestige_menu),
                 widget::button("Quit to title", quit_to_title),
             ]
         ),],
@@ -56,7 +94,7 @@ fn text_block() -> impl Bundle {
         },
         children![
             widget::label(
-                "Congrats on hitting the ultimate collection rate! Here's a little twist to go with your success..."
+                "Congrats on reaching your goals! Here's a little twist to go with your success..."
             ),
             widget::label(
                 "As an AI purifier, you found a brilliant loophole: making more pollution was actually the fastest way to boost your stats. Who knew? Your efficiency ratings went through the roof - because you accidentally became part of the problem you were supposed to fix."
@@ -74,6 +112,10 @@ fn close_menu(_: Trigger<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>
     next_menu.set(Menu::None);
 }
 
+fn open_prestige_menu(_: Trigger<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Prestige);
+}
+
 fn quit_to_title(_: Trigger<Pointer<Click>>, mut next_screen: ResMut<NextState<Screen>>) {
     next_screen.set(Screen::Title);
 }
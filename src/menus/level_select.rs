@@ -0,0 +1,76 @@
+//! The level select menu, opened by "Play" in the main menu.
+
+use bevy::{ecs::spawn::SpawnWith, input::common_conditions::input_just_pressed};
+
+use crate::prelude::*;
+
+use crate::{
+    demo::{
+        UpgradeCatalog,
+        currency::NumberFormat,
+        level::{CurrentLevel, catalog::LevelCatalog},
+    },
+    menus::Menu,
+    theme::widget,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Menu::LevelSelect), spawn_level_select_menu);
+    app.add_systems(
+        Update,
+        go_back.run_if(in_state(Menu::LevelSelect).and(input_just_pressed(KeyCode::Escape))),
+    );
+}
+
+fn spawn_level_select_menu(
+    mut commands: Commands,
+    catalog: Option<Res<LevelCatalog>>,
+    upgrades: Option<Res<UpgradeCatalog>>,
+    format: Res<NumberFormat>,
+) {
+    // goals name upgrades, so they are described while the catalogs are at hand
+    let levels = catalog.zip(upgrades).map(|(catalog, upgrades)| {
+        catalog
+            .levels
+            .iter()
+            .map(|level| (level.clone(), level.describe_objectives(&upgrades, *format)))
+            .collect::<Vec<_>>()
+    });
+    commands.spawn((
+        widget::ui_root("Level Select Menu"),
+        GlobalZIndex(2),
+        StateScoped(Menu::LevelSelect),
+        Children::spawn(SpawnWith(move |parent: &mut ChildSpawner| {
+            parent.spawn(widget::header("Choose a level"));
+            let Some(levels) = levels else {
+                parent.spawn(widget::label("Still loading, try again in a moment."));
+                parent.spawn(widget::button("Back", go_back_on_click));
+                return;
+            };
+            for (level, goals) in levels {
+                parent.spawn(widget::label(format!(
+                    "{} Goals: {goals}",
+                    level.description
+                )));
+                parent.spawn(widget::button(
+                    level.name.clone(),
+                    move |_: Trigger<Pointer<Click>>,
+                          mut commands: Commands,
+                          mut next_screen: ResMut<NextState<Screen>>| {
+                        commands.insert_resource(CurrentLevel(level.clone()));
+                        next_screen.set(Screen::Gameplay);
+                    },
+                ));
+            }
+            parent.spawn(widget::button("Back", go_back_on_click));
+        })),
+    ));
+}
+
+fn go_back_on_click(_: Trigger<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Main);
+}
+
+fn go_back(mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Main);
+}
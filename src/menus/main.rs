@@ -1,41 +1,73 @@
 //! The main menu (seen on the title screen).
 
-use bevy::prelude::*;
+use bevy::{ecs::spawn::SpawnWith, prelude::*};
 
-use crate::{menus::Menu, screens::Screen, theme::widget};
+use crate::{
+    demo::{
+        level::{CurrentLevel, catalog::LevelCatalog},
+        prestige::MetaProgress,
+        save::{PendingRestore, SaveGame},
+    },
+    menus::Menu,
+    screens::Screen,
+    theme::widget,
+};
 
 pub(super) fn plugin(app: &mut App) {
     app.add_systems(OnEnter(Menu::Main), spawn_main_menu);
 }
 
-fn spawn_main_menu(mut commands: Commands) {
+fn spawn_main_menu(mut commands: Commands, meta: Res<MetaProgress>) {
+    let save = SaveGame::load();
+    let has_prestiged = meta.prestiges > 0;
     commands.spawn((
         widget::ui_root("Main Menu"),
         GlobalZIndex(2),
         StateScoped(Menu::Main),
-        #[cfg(not(target_family = "wasm"))]
-        children![
-            widget::header("A Lightning Air Cleaner"),
-            widget::button("Play", enter_loading_or_gameplay_screen),
-            widget::button("Settings", open_settings_menu),
-            widget::button("Credits", open_credits_menu),
-            widget::button("Exit", exit_app),
-        ],
-        #[cfg(target_family = "wasm")]
-        children![
-            widget::header("A Lightning Air Cleaner"),
-            widget::button("Play", enter_loading_or_gameplay_screen),
-            widget::button("Settings", open_settings_menu),
-            widget::button("Credits", open_credits_menu),
-        ],
+        Children::spawn(SpawnWith(move |parent: &mut ChildSpawner| {
+            parent.spawn(widget::header("A Lightning Air Cleaner"));
+            if let Some(save) = save {
+                parent.spawn(widget::button(
+                    "Continue",
+                    move |_: Trigger<Pointer<Click>>,
+                          mut commands: Commands,
+                          levels: Option<Res<LevelCatalog>>,
+                          mut next_screen: ResMut<NextState<Screen>>| {
+                        // saves from before levels existed continue the first one
+                        if let Some(level) = levels
+                            .as_ref()
+                            .and_then(|levels| levels.get(&save.level).or(levels.levels.first()))
+                        {
+                            commands.insert_resource(CurrentLevel(level.clone()));
+                        }
+                        commands.insert_resource(PendingRestore(save.clone()));
+                        next_screen.set(Screen::Gameplay);
+                    },
+                ));
+            }
+            parent.spawn(widget::button("Play", open_level_select_menu));
+            if has_prestiged {
+                parent.spawn(widget::button("Purifier Core", open_prestige_menu));
+            }
+            parent.spawn(widget::button("Achievements", open_achievements_menu));
+            parent.spawn(widget::button("Settings", open_settings_menu));
+            parent.spawn(widget::button("Credits", open_credits_menu));
+            #[cfg(not(target_family = "wasm"))]
+            parent.spawn(widget::button("Exit", exit_app));
+        })),
     ));
 }
 
-fn enter_loading_or_gameplay_screen(
-    _: Trigger<Pointer<Click>>,
-    mut next_screen: ResMut<NextState<Screen>>,
-) {
-    next_screen.set(Screen::Gameplay);
+fn open_level_select_menu(_: Trigger<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::LevelSelect);
+}
+
+fn open_prestige_menu(_: Trigger<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Prestige);
+}
+
+fn open_achievements_menu(_: Trigger<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Achievements);
 }
 
 fn open_settings_menu(_: Trigger<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
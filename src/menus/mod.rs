@@ -1,14 +1,20 @@
 //! The game's menus and transitions between them.
 
+mod achievements;
 mod complete;
 mod credits;
+mod level_select;
 mod main;
+mod offline;
 mod pause;
+mod prestige;
+mod run_lost;
 mod settings;
+mod statistics;
 
 use bevy::prelude::*;
 
-pub use complete::{COMPLETE_COLLECTION_RATE, CompleteTheGame};
+pub use complete::CompleteTheGame;
 
 pub(super) fn plugin(app: &mut App) {
     app.init_state::<Menu>();
@@ -19,6 +25,12 @@ pub(super) fn plugin(app: &mut App) {
         settings::plugin,
         pause::plugin,
         complete::plugin,
+        offline::plugin,
+        run_lost::plugin,
+        prestige::plugin,
+        level_select::plugin,
+        achievements::plugin,
+        statistics::plugin,
     ));
 }
 
@@ -32,4 +44,10 @@ pub enum Menu {
     Settings,
     Pause,
     Complete,
+    OfflineProgress,
+    RunLost,
+    Prestige,
+    LevelSelect,
+    Achievements,
+    Statistics,
 }
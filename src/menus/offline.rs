@@ -0,0 +1,55 @@
+//! The "While you were away" menu, shown when continuing a saved run.
+
+use crate::prelude::*;
+
+use crate::{
+    demo::{
+        currency::NumberFormat,
+        offline::{OfflineEarnings, format_duration},
+    },
+    menus::Menu,
+    theme::widget,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Menu::OfflineProgress), spawn_offline_progress_menu);
+}
+
+fn spawn_offline_progress_menu(
+    mut commands: Commands,
+    earnings: Res<OfflineEarnings>,
+    format: Res<NumberFormat>,
+) {
+    info!("Opening offline progress menu");
+    let away = if earnings.paid_secs < earnings.away_secs {
+        format!(
+            "You were away for {} (capped at {}).",
+            format_duration(earnings.away_secs),
+            format_duration(earnings.paid_secs)
+        )
+    } else {
+        format!("You were away for {}.", format_duration(earnings.away_secs))
+    };
+    commands.spawn((
+        widget::ui_root("Offline Progress Menu"),
+        GlobalZIndex(2),
+        StateScoped(Menu::OfflineProgress),
+        BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.8)),
+        children![
+            widget::header("While you were away"),
+            widget::label(away),
+            widget::label(format!(
+                "Collect rate {:.1}/s + data center {:.1}/s at {:.0}% efficiency",
+                earnings.collect_rate,
+                earnings.passive_rate,
+                earnings.efficiency * 100.0
+            )),
+            widget::label(format!("+{} Dust Data", format.format(earnings.dust_data))),
+            widget::button("Collect", close_menu),
+        ],
+    ));
+}
+
+fn close_menu(_: Trigger<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::None);
+}
@@ -0,0 +1,150 @@
+//! The prestige menu: reset a completed run for insight and spend it on meta
+//! upgrades.
+//!
+//! Opened from the complete menu during gameplay, and from the main menu to
+//! spend insight between runs.
+
+use bevy::{ecs::spawn::SpawnWith, input::common_conditions::input_just_pressed};
+
+use crate::prelude::*;
+
+use crate::{
+    demo::{
+        Inventory,
+        currency::NumberFormat,
+        prestige::{MetaProgress, MetaUpgrade},
+    },
+    menus::Menu,
+    theme::widget,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Menu::Prestige), spawn_prestige_menu);
+    app.add_systems(
+        Update,
+        go_back.run_if(in_state(Menu::Prestige).and(input_just_pressed(KeyCode::Escape))),
+    );
+    app.add_observer(rebuild_prestige_menu);
+}
+
+/// Spawn the menu again after a meta upgrade was bought.
+#[derive(Event, Debug)]
+struct PrestigeMenuChanged;
+
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+struct PrestigeMenu;
+
+fn spawn_prestige_menu(
+    mut commands: Commands,
+    meta: Res<MetaProgress>,
+    inventory: Res<Inventory>,
+    screen: Res<State<Screen>>,
+    format: Res<NumberFormat>,
+) {
+    // the title screen only spends insight, there is no run to reset
+    let pending = (screen.get() == &Screen::Gameplay)
+        .then(|| format.format(MetaProgress::insight_for(inventory.collected)));
+    let upgrades = MetaUpgrade::ALL.map(|upgrade| {
+        let spec = upgrade.spec();
+        let text = if meta.is_maxed(upgrade) {
+            format!("{} ({}): done", spec.name, spec.tips)
+        } else if !meta.is_unlocked(upgrade) {
+            let required = spec.requires.map_or("", |required| required.spec().name);
+            format!("{} (needs {required})", spec.name)
+        } else {
+            format!(
+                "{} {}/{} ({}): {} insight",
+                spec.name,
+                meta.level(upgrade),
+                spec.max_level,
+                spec.tips,
+                format.format(meta.cost(upgrade))
+            )
+        };
+        (upgrade, text)
+    });
+    let insight = format.format(meta.insight);
+    commands.spawn((
+        widget::ui_root("Prestige Menu"),
+        PrestigeMenu,
+        GlobalZIndex(2),
+        StateScoped(Menu::Prestige),
+        BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.95)),
+        Children::spawn(SpawnWith(move |parent: &mut ChildSpawner| {
+            parent.spawn(widget::header("Purifier Core"));
+            parent.spawn(widget::label(format!("Insight: {insight}")));
+            for (upgrade, text) in upgrades {
+                parent.spawn(widget::button_base(
+                    text,
+                    TextFont::from_font_size(20.0),
+                    move |_: Trigger<Pointer<Click>>,
+                          mut meta: ResMut<MetaProgress>,
+                          mut commands: Commands| {
+                        if meta.purchase(upgrade) {
+                            commands.trigger(PrestigeMenuChanged);
+                        } else {
+                            info!("Can't buy this meta upgrade.");
+                        }
+                    },
+                    Node {
+                        width: Val::Px(600.0),
+                        padding: UiRect::all(Val::Px(5.0)),
+                        justify_content: JustifyContent::Center,
+                        ..default()
+                    },
+                ));
+            }
+            if let Some(pending) = pending {
+                parent.spawn(widget::label(
+                    "Resetting the run keeps only insight and meta upgrades.",
+                ));
+                parent.spawn(widget::button(format!("Reset for +{pending}"), reset_run));
+            }
+            parent.spawn(widget::button("Back", go_back_on_click));
+        })),
+    ));
+}
+
+fn rebuild_prestige_menu(
+    _: Trigger<PrestigeMenuChanged>,
+    mut commands: Commands,
+    menus: Query<Entity, With<PrestigeMenu>>,
+) {
+    for menu in &menus {
+        commands.entity(menu).despawn();
+    }
+    commands.run_system_cached(spawn_prestige_menu);
+}
+
+fn reset_run(
+    _: Trigger<Pointer<Click>>,
+    mut commands: Commands,
+    mut meta: ResMut<MetaProgress>,
+    inventory: Res<Inventory>,
+    mut next_screen: ResMut<NextState<Screen>>,
+) {
+    meta.prestige(&inventory, &mut commands);
+    // the next run starts from the title screen
+    next_screen.set(Screen::Title);
+}
+
+fn go_back_on_click(
+    _: Trigger<Pointer<Click>>,
+    screen: Res<State<Screen>>,
+    mut next_menu: ResMut<NextState<Menu>>,
+) {
+    next_menu.set(back_to(screen.get()));
+}
+
+fn go_back(screen: Res<State<Screen>>, mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(back_to(screen.get()));
+}
+
+fn back_to(screen: &Screen) -> Menu {
+    if screen == &Screen::Title {
+        Menu::Main
+    } else {
+        Menu::Complete
+    }
+}
@@ -0,0 +1,29 @@
+//! The menu shown when the pollution meter overflows and the run is lost.
+
+use crate::prelude::*;
+
+use crate::{menus::Menu, screens::Screen, theme::widget};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Menu::RunLost), spawn_run_lost_menu);
+}
+
+fn spawn_run_lost_menu(mut commands: Commands) {
+    info!("Opening run lost menu");
+    commands.spawn((
+        widget::ui_root("Run Lost Menu"),
+        GlobalZIndex(2),
+        StateScoped(Menu::RunLost),
+        BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.95)),
+        children![
+            widget::header("The City Is Smothered"),
+            widget::label("Too much dust slipped past your discharge points."),
+            widget::label("Place them to cover where the dust falls, and try again."),
+            widget::button("Quit to title", quit_to_title),
+        ],
+    ));
+}
+
+fn quit_to_title(_: Trigger<Pointer<Click>>, mut next_screen: ResMut<NextState<Screen>>) {
+    next_screen.set(Screen::Title);
+}
@@ -4,7 +4,7 @@
 
 use bevy::{audio::Volume, input::common_conditions::input_just_pressed, prelude::*, ui::Val::*};
 
-use crate::{menus::Menu, screens::Screen, theme::prelude::*};
+use crate::{demo::currency::NumberFormat, menus::Menu, screens::Screen, theme::prelude::*};
 
 pub(super) fn plugin(app: &mut App) {
     app.add_systems(OnEnter(Menu::Settings), spawn_settings_menu);
@@ -14,9 +14,10 @@ pub(super) fn plugin(app: &mut App) {
     );
 
     app.register_type::<GlobalVolumeLabel>();
+    app.register_type::<NumberFormatLabel>();
     app.add_systems(
         Update,
-        update_global_volume_label.run_if(in_state(Menu::Settings)),
+        (update_global_volume_label, update_number_format_label).run_if(in_state(Menu::Settings)),
     );
 }
 
@@ -52,6 +53,14 @@ fn settings_grid() -> impl Bundle {
                 }
             ),
             global_volume_widget(),
+            (
+                widget::label("Number Format"),
+                Node {
+                    justify_self: JustifySelf::End,
+                    ..default()
+                }
+            ),
+            number_format_widget(),
         ],
     )
 }
@@ -104,6 +113,38 @@ fn update_global_volume_label(
     label.0 = format!("{percent:3.0}%");
 }
 
+fn number_format_widget() -> impl Bundle {
+    (
+        Name::new("Number Format Widget"),
+        Node {
+            justify_self: JustifySelf::Start,
+            align_items: AlignItems::Center,
+            column_gap: Px(10.0),
+            ..default()
+        },
+        children![
+            widget::button_small(">", next_number_format),
+            (widget::label(""), NumberFormatLabel),
+        ],
+    )
+}
+
+fn next_number_format(_: Trigger<Pointer<Click>>, mut format: ResMut<NumberFormat>) {
+    *format = format.next();
+    format.store();
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct NumberFormatLabel;
+
+fn update_number_format_label(
+    format: Res<NumberFormat>,
+    mut label: Single<&mut Text, With<NumberFormatLabel>>,
+) {
+    label.0 = format!("{} ({})", format.label(), format.format(1_234_567u32));
+}
+
 fn go_back_on_click(
     _: Trigger<Pointer<Click>>,
     screen: Res<State<Screen>>,
@@ -0,0 +1,158 @@
+//! The statistics menu, opened from the pause and complete menus.
+
+use bevy::{ecs::spawn::SpawnWith, input::common_conditions::input_just_pressed};
+
+use crate::prelude::*;
+
+use crate::{
+    demo::{
+        currency::NumberFormat,
+        statistics::{Counters, Statistics},
+    },
+    menus::Menu,
+    theme::{palette::LABEL_TEXT, widget},
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<StatisticsOpenedFrom>();
+    app.add_systems(OnEnter(Menu::Statistics), spawn_statistics_menu);
+    app.add_systems(
+        Update,
+        go_back.run_if(in_state(Menu::Statistics).and(input_just_pressed(KeyCode::Escape))),
+    );
+}
+
+/// The menu "Back" returns to.
+#[derive(Resource, Debug, Default)]
+pub(super) struct StatisticsOpenedFrom(Menu);
+
+/// A button action opening the statistics menu, returning to `from`.
+pub(super) fn open_statistics_menu(
+    from: Menu,
+) -> impl Fn(Trigger<Pointer<Click>>, ResMut<StatisticsOpenedFrom>, ResMut<NextState<Menu>>) {
+    move |_, mut opened_from, mut next_menu| {
+        opened_from.0 = from;
+        next_menu.set(Menu::Statistics);
+    }
+}
+
+fn spawn_statistics_menu(
+    mut commands: Commands,
+    statistics: Res<Statistics>,
+    format: Res<NumberFormat>,
+) {
+    let rows = rows(&statistics.run, &statistics.lifetime, *format);
+    commands.spawn((
+        widget::ui_root("Statistics Menu"),
+        GlobalZIndex(2),
+        StateScoped(Menu::Statistics),
+        BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.95)),
+        Children::spawn(SpawnWith(move |parent: &mut ChildSpawner| {
+            parent.spawn(widget::header("Statistics"));
+            parent.spawn(row(
+                "".to_string(),
+                "This run".to_string(),
+                "Lifetime".to_string(),
+            ));
+            for (label, run, lifetime) in rows {
+                parent.spawn(row(label, run, lifetime));
+            }
+            parent.spawn(widget::button("Back", go_back_on_click));
+        })),
+    ));
+}
+
+fn rows(
+    run: &Counters,
+    lifetime: &Counters,
+    format: NumberFormat,
+) -> Vec<(String, String, String)> {
+    let both = |label: &str, value: &dyn Fn(&Counters) -> String| {
+        (label.to_string(), value(run), value(lifetime))
+    };
+    let mut rows = vec![
+        both("Time played", &|counters| {
+            duration_text(counters.time_played)
+        }),
+        both("Dust destroyed", &|counters| {
+            counters.destroyed_total().to_string()
+        }),
+    ];
+    // only the species that were ever destroyed
+    for dust in lifetime.destroyed.keys() {
+        rows.push(both(&format!("  {dust:?}"), &|counters| {
+            counters
+                .destroyed
+                .get(dust)
+                .copied()
+                .unwrap_or_default()
+                .to_string()
+        }));
+    }
+    rows.extend([
+        both("Dust escaped", &|counters| counters.escaped.to_string()),
+        both("Pollution leaked", &|counters| {
+            format!("{:.0}", counters.leaked)
+        }),
+        both("Damage dealt", &|counters| {
+            format!("{:.0}", counters.damage_dealt)
+        }),
+        both("Longest chain", &|counters| {
+            counters.longest_chain.to_string()
+        }),
+        both("Energy spent", &|counters| {
+            format!("{:.0}", counters.energy_spent)
+        }),
+        both("Dust data earned", &|counters| {
+            format.format(counters.data_earned)
+        }),
+        both("Dust data spent", &|counters| {
+            format.format(counters.data_spent)
+        }),
+    ]);
+    rows
+}
+
+fn duration_text(seconds: f64) -> String {
+    let seconds = seconds as u64;
+    format!(
+        "{}h {:02}m {:02}s",
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60
+    )
+}
+
+fn row(label: String, run: String, lifetime: String) -> impl Bundle {
+    let cell = |text: String, width: f32| {
+        (
+            Node {
+                width: Val::Px(width),
+                ..default()
+            },
+            Text(text),
+            TextFont::from_font_size(20.0),
+            TextColor(LABEL_TEXT),
+        )
+    };
+    (
+        Name::new("Statistics Row"),
+        Node {
+            flex_direction: FlexDirection::Row,
+            ..default()
+        },
+        children![cell(label, 240.0), cell(run, 180.0), cell(lifetime, 180.0)],
+    )
+}
+
+fn go_back_on_click(
+    _: Trigger<Pointer<Click>>,
+    opened_from: Res<StatisticsOpenedFrom>,
+    mut next_menu: ResMut<NextState<Menu>>,
+) {
+    next_menu.set(opened_from.0);
+}
+
+fn go_back(opened_from: Res<StatisticsOpenedFrom>, mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(opened_from.0);
+}
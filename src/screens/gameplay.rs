@@ -4,7 +4,10 @@ use bevy::{input::common_conditions::input_just_pressed, prelude::*, ui::Val::*}
 
 use crate::{
     Pause,
-    demo::level::spawn_level,
+    demo::{
+        level::{run_lost, spawn_level},
+        offline::OfflineEarnings,
+    },
     menus::{CompleteTheGame, Menu},
     screens::Screen,
 };
@@ -32,6 +35,26 @@ pub(super) fn plugin(app: &mut App) {
         ),
     );
 
+    // show offline earnings of a continued run
+    app.add_systems(
+        Update,
+        (pause, open_offline_progress_menu).run_if(
+            in_state(Screen::Gameplay)
+                .and(in_state(Menu::None))
+                .and(resource_exists::<OfflineEarnings>),
+        ),
+    );
+
+    // the level's loss condition is met
+    app.add_systems(
+        Update,
+        (pause, open_run_lost_menu).run_if(
+            in_state(Screen::Gameplay)
+                .and(in_state(Menu::None))
+                .and(run_lost),
+        ),
+    );
+
     app.add_systems(OnExit(Screen::Gameplay), (close_menu, unpause));
     app.add_systems(
         OnEnter(Menu::None),
@@ -65,10 +88,28 @@ fn open_pause_menu(mut next_menu: ResMut<NextState<Menu>>) {
     next_menu.set(Menu::Pause);
 }
 
-fn open_complete_menu(mut next_menu: ResMut<NextState<Menu>>) {
+fn open_complete_menu(
+    mut events: EventReader<CompleteTheGame>,
+    mut next_menu: ResMut<NextState<Menu>>,
+) {
+    for event in events.read() {
+        info!(
+            "Level complete after {:.0}s, {} objectives met",
+            event.elapsed.as_secs_f32(),
+            event.objectives.len()
+        );
+    }
     next_menu.set(Menu::Complete);
 }
 
+fn open_offline_progress_menu(mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::OfflineProgress);
+}
+
+fn open_run_lost_menu(mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::RunLost);
+}
+
 fn close_menu(mut next_menu: ResMut<NextState<Menu>>) {
     next_menu.set(Menu::None);
 }
@@ -1,12 +1,14 @@
 //! A splash screen that plays briefly at startup.
 
 use bevy::{
+    asset::UntypedAssetLoadFailedEvent,
+    ecs::spawn::SpawnIter,
     image::{ImageLoaderSettings, ImageSampler},
     input::common_conditions::input_just_pressed,
     prelude::*,
 };
 
-use crate::{AssetsState, screens::Screen, theme::prelude::*};
+use crate::{AssetsState, LoadErrors, screens::Screen, theme::prelude::*};
 
 pub(super) fn plugin(app: &mut App) {
     // Spawn splash screen.
@@ -44,6 +46,14 @@ pub(super) fn plugin(app: &mut App) {
                 .and(in_state(AssetsState::Done)),
         ),
     );
+
+    // Show why loading failed instead of waiting for it forever.
+    app.init_resource::<LoadErrors>();
+    app.add_systems(
+        Update,
+        record_load_errors.run_if(in_state(AssetsState::Loading)),
+    );
+    app.add_systems(OnEnter(AssetsState::Failed), spawn_load_error_screen);
 }
 
 const SPLASH_BACKGROUND_COLOR: Color = Color::srgb(0.157, 0.157, 0.157);
@@ -147,3 +157,26 @@ fn check_splash_timer(timer: ResMut<SplashTimer>, mut next_screen: ResMut<NextSt
 fn enter_title_screen(mut next_screen: ResMut<NextState<Screen>>) {
     next_screen.set(Screen::Title);
 }
+
+fn record_load_errors(
+    mut events: EventReader<UntypedAssetLoadFailedEvent>,
+    mut errors: ResMut<LoadErrors>,
+) {
+    for event in events.read() {
+        errors.0.push(event.error.to_string());
+    }
+}
+
+fn spawn_load_error_screen(mut commands: Commands, errors: Res<LoadErrors>) {
+    error!("Could not load the game: {:?}", errors.0);
+    commands.spawn((
+        widget::ui_root("Load Error Screen"),
+        BackgroundColor(SPLASH_BACKGROUND_COLOR),
+        GlobalZIndex(3),
+        Children::spawn((
+            Spawn(widget::header("Could not load the game")),
+            SpawnIter(errors.0.clone().into_iter().map(widget::label)),
+            Spawn(widget::label("See the log for details.")),
+        )),
+    ));
+}
@@ -0,0 +1,111 @@
+//! Persistent key-value storage for save games.
+//!
+//! Native builds write one file per key into the user's data directory, web
+//! builds use `localStorage`.
+
+use std::io;
+
+use bevy::prelude::*;
+
+/// Read the value stored under `key`, `None` if nothing was stored yet.
+pub fn load(key: &str) -> Option<String> {
+    match platform::load(key) {
+        Ok(value) => value,
+        Err(error) => {
+            warn!("Could not read `{key}`: {error}");
+            None
+        }
+    }
+}
+
+/// Store `value` under `key`, replacing the previous value.
+pub fn save(key: &str, value: &str) -> io::Result<()> {
+    platform::save(key, value)
+}
+
+/// Forget the value stored under `key`, if any.
+pub fn remove(key: &str) -> io::Result<()> {
+    platform::remove(key)
+}
+
+#[cfg(not(target_family = "wasm"))]
+mod platform {
+    use std::{fs, io, path::PathBuf};
+
+    fn path(key: &str) -> PathBuf {
+        let data_dir = std::env::var_os("APPDATA")
+            .or_else(|| std::env::var_os("XDG_DATA_HOME"))
+            .map(PathBuf::from)
+            .or_else(|| {
+                std::env::var_os("HOME").map(|home| {
+                    let home = PathBuf::from(home);
+                    if cfg!(target_os = "macos") {
+                        home.join("Library/Application Support")
+                    } else {
+                        home.join(".local/share")
+                    }
+                })
+            })
+            .unwrap_or_else(|| PathBuf::from("."));
+        data_dir.join("bevy_jam_6").join(format!("{key}.ron"))
+    }
+
+    pub fn load(key: &str) -> io::Result<Option<String>> {
+        match fs::read_to_string(path(key)) {
+            Ok(value) => Ok(Some(value)),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(error) => Err(error),
+        }
+    }
+
+    pub fn save(key: &str, value: &str) -> io::Result<()> {
+        let path = path(key);
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        // write to a temporary file first so a crash can't leave half a save behind
+        let temp = path.with_extension("ron.tmp");
+        fs::write(&temp, value)?;
+        fs::rename(temp, path)
+    }
+
+    pub fn remove(key: &str) -> io::Result<()> {
+        match fs::remove_file(path(key)) {
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(()),
+            result => result,
+        }
+    }
+}
+
+#[cfg(target_family = "wasm")]
+mod platform {
+    use std::io;
+
+    fn storage() -> io::Result<web_sys::Storage> {
+        web_sys::window()
+            .and_then(|window| window.local_storage().ok().flatten())
+            .ok_or_else(|| io::Error::other("localStorage is unavailable"))
+    }
+
+    fn key(key: &str) -> String {
+        format!("bevy_jam_6/{key}")
+    }
+
+    pub fn load(key_name: &str) -> io::Result<Option<String>> {
+        storage()?
+            .get_item(&key(key_name))
+            .map_err(|error| io::Error::other(format!("{error:?}")))
+    }
+
+    pub fn save(key_name: &str, value: &str) -> io::Result<()> {
+        storage()?
+            .set_item(&key(key_name), value)
+            .map_err(|error| io::Error::other(format!("{error:?}")))
+    }
+
+    pub fn remove(key_name: &str) -> io::Result<()> {
+        storage()?
+            .remove_item(&key(key_name))
+            .map_err(|error| io::Error::other(format!("{error:?}")))
+    }
+}
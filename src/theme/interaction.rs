@@ -1,6 +1,6 @@
 use crate::prelude::*;
 
-use crate::audio::sound_effect;
+use crate::{audio::sound_effect, theme::palette::*};
 
 pub(super) fn plugin(app: &mut App) {
     app.configure_loading_state(
@@ -8,6 +8,7 @@ pub(super) fn plugin(app: &mut App) {
     );
 
     app.register_type::<InteractionPalette>();
+    app.register_type::<InteractionDisabled>();
     app.add_systems(Update, apply_interaction_palette);
 
     app.register_type::<InteractionAssets>();
@@ -24,21 +25,55 @@ pub struct InteractionPalette {
     pub none: Color,
     pub hovered: Color,
     pub pressed: Color,
+    /// Used whatever the interaction while [`InteractionDisabled`].
+    pub disabled: Color,
 }
 
+impl InteractionPalette {
+    /// The palette of the regular buttons.
+    pub const BUTTON: Self = Self {
+        none: BUTTON_BACKGROUND,
+        hovered: BUTTON_HOVERED_BACKGROUND,
+        pressed: BUTTON_PRESSED_BACKGROUND,
+        disabled: BUTTON_DISABLED_BACKGROUND,
+    };
+}
+
+/// Shows a widget with an [`InteractionPalette`] greyed out, e.g. a button
+/// whose action can't be afforded. Clicks still reach its observers.
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+pub struct InteractionDisabled;
+
 fn apply_interaction_palette(
-    mut palette_query: Query<
-        (&Interaction, &InteractionPalette, &mut BackgroundColor),
-        Changed<Interaction>,
-    >,
+    mut palette_query: Query<(
+        Entity,
+        Ref<Interaction>,
+        &InteractionPalette,
+        &mut BackgroundColor,
+        Has<InteractionDisabled>,
+    )>,
+    disabled: Query<(), Added<InteractionDisabled>>,
+    mut enabled: RemovedComponents<InteractionDisabled>,
 ) {
-    for (interaction, palette, mut background) in &mut palette_query {
-        *background = match interaction {
-            Interaction::None => palette.none,
-            Interaction::Hovered => palette.hovered,
-            Interaction::Pressed => palette.pressed,
+    let enabled = enabled.read().collect::<Vec<_>>();
+    for (entity, interaction, palette, mut background, is_disabled) in &mut palette_query {
+        if interaction.is_changed() || disabled.contains(entity) || enabled.contains(&entity) {
+            *background = palette.color(&interaction, is_disabled).into();
+        }
+    }
+}
+
+impl InteractionPalette {
+    fn color(&self, interaction: &Interaction, disabled: bool) -> Color {
+        if disabled {
+            return self.disabled;
+        }
+        match interaction {
+            Interaction::None => self.none,
+            Interaction::Hovered => self.hovered,
+            Interaction::Pressed => self.pressed,
         }
-        .into();
     }
 }
 
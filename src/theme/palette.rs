@@ -14,5 +14,9 @@ pub const BUTTON_BACKGROUND: Color = Color::srgb(0.275, 0.400, 0.750);
 pub const BUTTON_HOVERED_BACKGROUND: Color = Color::srgb(0.384, 0.600, 0.820);
 /// #3d4999
 pub const BUTTON_PRESSED_BACKGROUND: Color = Color::srgb(0.239, 0.286, 0.600);
+/// #5c5f6b
+pub const BUTTON_DISABLED_BACKGROUND: Color = Color::srgb(0.361, 0.373, 0.420);
 
 pub const PURCHASE_ROW_BACKGROUND: Color = Color::srgba(0.169, 0.169, 0.169, 0.5);
+
+pub const TOOLTIP_BACKGROUND: Color = Color::srgba(0.05, 0.05, 0.05, 0.9);
@@ -0,0 +1,87 @@
+//! Tooltips: a short explanation shown next to the cursor while a node is hovered.
+
+use bevy::{
+    picking::{hover::HoverMap, pointer::PointerId},
+    window::PrimaryWindow,
+};
+
+use crate::prelude::*;
+
+use super::palette::{BUTTON_TEXT, TOOLTIP_BACKGROUND};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<Tooltip>();
+    app.add_systems(Update, show_tooltip);
+}
+
+/// Text shown while the node or one of its descendants is hovered. Spawn it
+/// with [`widget::tooltip`](super::widget::tooltip).
+#[derive(Component, Reflect, Debug, Clone)]
+#[reflect(Component)]
+pub struct Tooltip(pub String);
+
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+struct TooltipPopup;
+
+/// Distance between the cursor and the popup's top left corner.
+const CURSOR_OFFSET: Vec2 = Vec2::new(16.0, 16.0);
+
+fn show_tooltip(
+    mut commands: Commands,
+    hover_map: Res<HoverMap>,
+    tooltips: Query<&Tooltip>,
+    ancestors: Query<&ChildOf>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    mut popup: Query<(Entity, &mut Node, &mut Text), With<TooltipPopup>>,
+) {
+    // the innermost tooltip around the hovered node
+    let tooltip = hover_map
+        .get(&PointerId::Mouse)
+        .into_iter()
+        .flat_map(|hovered| hovered.keys())
+        .find_map(|&entity| {
+            std::iter::once(entity)
+                .chain(ancestors.iter_ancestors(entity))
+                .find_map(|entity| tooltips.get(entity).ok())
+        });
+    let cursor = windows
+        .single()
+        .ok()
+        .and_then(|window| window.cursor_position());
+
+    let (Some(tooltip), Some(cursor)) = (tooltip, cursor) else {
+        for (entity, ..) in &popup {
+            commands.entity(entity).despawn();
+        }
+        return;
+    };
+    let position = cursor + CURSOR_OFFSET;
+    if let Ok((_, mut node, mut text)) = popup.single_mut() {
+        node.left = Val::Px(position.x);
+        node.top = Val::Px(position.y);
+        if text.0 != tooltip.0 {
+            text.0.clone_from(&tooltip.0);
+        }
+        return;
+    }
+    commands.spawn((
+        Name::new("Tooltip"),
+        TooltipPopup,
+        Node {
+            position_type: PositionType::Absolute,
+            left: Val::Px(position.x),
+            top: Val::Px(position.y),
+            max_width: Val::Px(300.0),
+            padding: UiRect::all(Val::Px(6.0)),
+            ..default()
+        },
+        Text(tooltip.0.clone()),
+        TextFont::from_font_size(16.0),
+        TextColor(BUTTON_TEXT),
+        BackgroundColor(TOOLTIP_BACKGROUND),
+        BorderRadius::all(Val::Px(5.0)),
+        GlobalZIndex(10),
+        Pickable::IGNORE,
+    ));
+}
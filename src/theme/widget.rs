@@ -8,7 +8,7 @@ use bevy::{
     ui::Val::*,
 };
 
-use crate::theme::{interaction::InteractionPalette, palette::*};
+use crate::theme::{interaction::InteractionPalette, palette::*, tooltip::Tooltip};
 
 /// A root UI node that fills the window and centers its content.
 pub fn ui_root(name: impl Into<Cow<'static, str>>) -> impl Bundle {
@@ -49,6 +49,11 @@ pub fn label(text: impl Into<String>) -> impl Bundle {
     )
 }
 
+/// Explains the node it is added to while it is hovered.
+pub fn tooltip(text: impl Into<String>) -> impl Bundle {
+    Tooltip(text.into())
+}
+
 /// A large rounded button with text and an action defined as an [`Observer`].
 pub fn button<E, B, M, I>(text: impl Into<String>, action: I) -> impl Bundle
 where
@@ -117,11 +122,7 @@ where
                     Name::new("Button Inner"),
                     Button,
                     BackgroundColor(BUTTON_BACKGROUND),
-                    InteractionPalette {
-                        none: BUTTON_BACKGROUND,
-                        hovered: BUTTON_HOVERED_BACKGROUND,
-                        pressed: BUTTON_PRESSED_BACKGROUND,
-                    },
+                    InteractionPalette::BUTTON,
                     children![(
                         Name::new("Button Text"),
                         Text(text),
@@ -136,3 +137,84 @@ where
         })),
     )
 }
+
+/// One bar of a [`bar_chart`], the `n`-th value counted from the oldest.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct ChartBar(pub usize);
+
+/// The title text of a [`bar_chart`].
+#[derive(Component, Debug, Clone, Copy)]
+pub struct ChartTitle;
+
+/// A small bar chart of the latest `bars` values, newest on the right. The
+/// title and every bar carry `marker`, set bar heights with [`chart_bar_height`].
+pub fn bar_chart<M: Component + Clone>(
+    title: impl Into<String>,
+    color: Color,
+    bars: usize,
+    marker: M,
+) -> impl Bundle {
+    let title = title.into();
+    (
+        Name::new("Bar Chart"),
+        Node {
+            flex_direction: FlexDirection::Column,
+            padding: UiRect::all(Px(5.0)),
+            ..default()
+        },
+        BorderRadius::all(Px(5.0)),
+        BackgroundColor(PURCHASE_ROW_BACKGROUND),
+        Children::spawn(SpawnWith(move |parent: &mut ChildSpawner| {
+            parent.spawn((
+                Name::new("Chart Title"),
+                Text(title),
+                TextFont::from_font_size(16.0),
+                TextColor(LABEL_TEXT),
+                ChartTitle,
+                marker.clone(),
+            ));
+            parent
+                .spawn((
+                    Name::new("Chart Bars"),
+                    Node {
+                        height: Px(40.0),
+                        align_items: AlignItems::End,
+                        column_gap: Px(1.0),
+                        ..default()
+                    },
+                ))
+                .with_children(|bars_parent| {
+                    for i in 0..bars {
+                        bars_parent.spawn((
+                            Name::new("Chart Bar"),
+                            Node {
+                                width: Px(2.0),
+                                height: Percent(0.0),
+                                ..default()
+                            },
+                            BackgroundColor(color),
+                            ChartBar(i),
+                            marker.clone(),
+                        ));
+                    }
+                });
+        })),
+    )
+}
+
+/// Height of `bar` when `values` (oldest first) are plotted in a chart of
+/// `bars` bars, scaled so the highest value fills the chart.
+pub fn chart_bar_height(values: &[f64], bars: usize, bar: ChartBar, max: f64) -> Val {
+    // the newest value sits in the last bar, older bars stay empty until filled
+    let offset = bars.saturating_sub(values.len());
+    let value = bar
+        .0
+        .checked_sub(offset)
+        .and_then(|i| values.get(i))
+        .copied()
+        .unwrap_or_default();
+    if max <= 0.0 {
+        return Percent(0.0);
+    }
+    Percent((value / max * 100.0).clamp(0.0, 100.0) as f32)
+}
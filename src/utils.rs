@@ -9,3 +9,18 @@ pub fn map_range(value: f32, from: Range<f32>, to: Range<f32>) -> f32 {
     let normalized_value = (value - from.start) / from_span;
     to.start + normalized_value * to_span
 }
+
+/// Wall-clock time in seconds since the Unix epoch.
+pub fn unix_time_secs() -> f64 {
+    #[cfg(target_family = "wasm")]
+    {
+        js_sys::Date::now() / 1000.0
+    }
+    #[cfg(not(target_family = "wasm"))]
+    {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|duration| duration.as_secs_f64())
+            .unwrap_or_default()
+    }
+}
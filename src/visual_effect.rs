@@ -1,7 +1,10 @@
-use crate::prelude::*;
+use crate::{demo::can_draw_gizmos, prelude::*};
 
 pub(super) fn plugin(app: &mut App) {
-    app.add_systems(Update, draw_attack_line);
+    app.add_systems(
+        Update,
+        (draw_attack_line, draw_attack_circle).run_if(can_draw_gizmos),
+    );
     app.add_systems(Update, remove_tempo_effect.in_set(AppSystems::TickTimers));
 }
 
@@ -24,11 +27,30 @@ impl TempoEffect {
 pub struct AttackLine {
     pub start: Vec2,
     pub end: Vec2,
+    pub color: Color,
+}
+
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+pub struct AttackCircle {
+    pub center: Vec2,
+    pub radius: f32,
+    pub color: Color,
 }
 
 fn draw_attack_line(query: Query<&AttackLine>, mut gizmos: Gizmos) {
     for attack_line in query.iter() {
-        gizmos.line_2d(attack_line.start, attack_line.end, Color::WHITE);
+        gizmos.line_2d(attack_line.start, attack_line.end, attack_line.color);
+    }
+}
+
+fn draw_attack_circle(query: Query<&AttackCircle>, mut gizmos: Gizmos) {
+    for attack_circle in query.iter() {
+        gizmos.circle_2d(
+            attack_circle.center,
+            attack_circle.radius,
+            attack_circle.color,
+        );
     }
 }
 